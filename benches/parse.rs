@@ -0,0 +1,159 @@
+//! Compares the single-pass parser against the previous allocating one.
+//!
+//! Run with `cargo bench --bench parse`.
+
+use duration_string::DurationString;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The previous parser, which collected every component into `String`s before
+/// converting them. Only the default options are kept and errors are dropped.
+mod legacy {
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    const UNITS: [(&str, u128); 10] = [
+        ("y", 31_556_926_000_000_000),
+        ("w", 604_800_000_000_000),
+        ("d", 86_400_000_000_000),
+        ("h", 3_600_000_000_000),
+        ("m", 60_000_000_000),
+        ("s", 1_000_000_000),
+        ("ms", 1_000_000),
+        ("us", 1000),
+        ("\u{b5}s", 1000),
+        ("ns", 1),
+    ];
+
+    struct Component {
+        quantity: String,
+        unit: String,
+    }
+
+    fn components(input: &str) -> Vec<Component> {
+        let mut components: Vec<Component> = Vec::new();
+        let mut in_unit = true;
+        for c in input.chars() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if c.is_ascii_digit() || c == '.' {
+                if in_unit {
+                    components.push(Component {
+                        quantity: String::new(),
+                        unit: String::new(),
+                    });
+                    in_unit = false;
+                }
+                if let Some(component) = components.last_mut() {
+                    component.quantity.push(c);
+                }
+            } else {
+                if components.is_empty() {
+                    components.push(Component {
+                        quantity: String::new(),
+                        unit: String::new(),
+                    });
+                }
+                in_unit = true;
+                if let Some(component) = components.last_mut() {
+                    component.unit.push(c);
+                }
+            }
+        }
+        components
+    }
+
+    fn quantity_in_nano(quantity: &str, unit_in_nano: u128) -> Option<u128> {
+        let (integer, fraction) = quantity.split_once('.').unwrap_or((quantity, ""));
+        if fraction.contains('.') || (integer.is_empty() && fraction.is_empty()) {
+            return None;
+        }
+        let integer = if integer.is_empty() {
+            0
+        } else {
+            integer.parse::<u64>().ok()?
+        };
+        let nanos = u128::from(integer).checked_mul(unit_in_nano)?;
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            return Some(nanos);
+        }
+        if fraction.len() > 19 {
+            return None;
+        }
+        let scale = fraction.bytes().fold(1u128, |scale, _| scale * 10);
+        let fraction = u128::from(fraction.parse::<u64>().ok()?) * unit_in_nano;
+        if !fraction.is_multiple_of(scale) {
+            return None;
+        }
+        nanos.checked_add(fraction / scale)
+    }
+
+    pub fn parse(input: &str) -> Option<Duration> {
+        if input.trim().is_empty() {
+            return None;
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        let mut total: u128 = 0;
+        for Component { quantity, unit } in components(input) {
+            let (symbol, unit_in_nano) = UNITS.iter().find(|(symbol, _)| *symbol == unit)?;
+            if quantity.is_empty() || seen.contains(symbol) {
+                return None;
+            }
+            seen.push(symbol);
+            total = total.checked_add(quantity_in_nano(&quantity, *unit_in_nano)?)?;
+        }
+        let secs = u64::try_from(total / 1_000_000_000).ok()?;
+        let nanos = u32::try_from(total % 1_000_000_000).ok()?;
+        Some(Duration::new(secs, nanos))
+    }
+}
+
+const INPUTS: [&str; 5] = [
+    "100ms",
+    "5m 30s",
+    "1h30m15s",
+    "1.5h",
+    "1y 2w 3d 4h 5m 6s 7ms 8us 9ns",
+];
+
+/// Runs `f` on every input until `budget` is spent, returning the time per parse.
+fn measure(budget: Duration, f: impl Fn(&str) -> Option<Duration>) -> Duration {
+    let start = Instant::now();
+    let mut parses: u32 = 0;
+    while start.elapsed() < budget {
+        for _ in 0..1000 {
+            for input in INPUTS {
+                black_box(f(black_box(input)));
+                parses += 1;
+            }
+        }
+    }
+    start.elapsed() / parses
+}
+
+fn main() {
+    for input in INPUTS {
+        let current = input.parse::<DurationString>().ok().map(Duration::from);
+        assert_eq!(
+            current,
+            legacy::parse(input),
+            "parsers disagree on {input:?}"
+        );
+    }
+
+    let budget = Duration::from_secs(1);
+    let legacy = measure(budget, legacy::parse);
+    let current = measure(budget, |input| {
+        DurationString::from_bytes(input.as_bytes())
+            .ok()
+            .map(Duration::from)
+    });
+    println!("legacy parser:      {legacy:>10?} per parse");
+    println!("single-pass parser: {current:>10?} per parse");
+    println!(
+        "speedup:            {:>10.2}x",
+        legacy.as_secs_f64() / current.as_secs_f64()
+    );
+}
@@ -0,0 +1,95 @@
+//! Procedural macros for `duration-string`, enabled with its `macros` feature.
+//!
+//! The grammar is shared with `duration-string` through copies of its `unit.rs`
+//! and `literal.rs`, updated with `make sync-macros`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use std::convert::TryFrom;
+use syn::{parse_macro_input, LitStr};
+
+#[allow(dead_code)]
+mod unit;
+
+mod literal;
+
+use literal::{parse_literal, LiteralError};
+use unit::SECOND_IN_NANO;
+
+/// A `DurationString` parsed from a string literal at compile time.
+///
+/// An invalid literal is a compile error pointing at the literal, such as
+/// `unknown unit "mz"`. The format is that of
+/// `DurationString::from_str_const`. The expansion is a constant expression.
+///
+/// ```rust
+/// use duration_string::{dur, DurationString};
+/// use std::time::Duration;
+///
+/// const TIMEOUT: DurationString = dur!("5m30s");
+/// assert_eq!(TIMEOUT, Duration::from_secs(330));
+/// ```
+///
+/// ```compile_fail
+/// let timeout = duration_string::dur!("5mz");
+/// ```
+#[proc_macro]
+pub fn dur(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let value = literal.value();
+    let nanos = match parse_literal(value.as_bytes()) {
+        Ok(nanos) => nanos,
+        Err(err) => {
+            let message = match err {
+                LiteralError::UnknownUnit(start, end) => format!(
+                    "unknown unit {:?}, expected ns, us, ms, s, m, h, d, w or y",
+                    &value[start..end]
+                ),
+                LiteralError::DuplicateUnit(start, end) => {
+                    format!("duplicate unit {:?}", &value[start..end])
+                }
+                _ => err.message().to_string(),
+            };
+            return syn::Error::new(literal.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let Ok(secs) = u64::try_from(nanos / SECOND_IN_NANO) else {
+        return syn::Error::new(literal.span(), LiteralError::Overflow.message())
+            .to_compile_error()
+            .into();
+    };
+    // The remainder is below one second and always fits in a `u32`
+    let subsec_nanos = u32::try_from(nanos % SECOND_IN_NANO).unwrap_or_default();
+    quote! {
+        ::duration_string::DurationString::new(
+            ::std::time::Duration::new(#secs, #subsec_nanos)
+        )
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_grammar_in_sync() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for file in ["unit.rs", "literal.rs"] {
+            // The originals only exist next to this crate in the repository
+            let Ok(original) = fs::read_to_string(root.join("../src").join(file)) else {
+                continue;
+            };
+            let copy = fs::read_to_string(root.join("src").join(file)).unwrap();
+            assert!(
+                copy == original,
+                "macros/src/{} differs from src/{}, run `make sync-macros`",
+                file,
+                file
+            );
+        }
+    }
+}
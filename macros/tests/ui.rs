@@ -0,0 +1,9 @@
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass.rs");
+    cases.compile_fail("tests/ui/unknown_unit.rs");
+    cases.compile_fail("tests/ui/duplicate_unit.rs");
+    cases.compile_fail("tests/ui/missing_unit.rs");
+    cases.compile_fail("tests/ui/not_a_string.rs");
+}
@@ -0,0 +1,9 @@
+use duration_string::{dur, DurationString};
+use std::time::Duration;
+
+const TIMEOUT: DurationString = dur!("5m30s");
+
+fn main() {
+    assert_eq!(TIMEOUT, Duration::from_secs(330));
+    assert_eq!(dur!(" 1.5 h "), Duration::from_secs(5400));
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = duration_string::dur!("5mz");
+}
@@ -0,0 +1,349 @@
+//! Clock notation such as `01:30:00`, `90:00` or `2d 03:00:00.250`.
+
+use crate::parser::{duration_from_nanos, quantity_in_nano};
+use crate::unit::{DAY_IN_NANO, HOUR_IN_NANO, MINUTE_IN_NANO, SECOND_IN_NANO};
+use crate::{DurationParser, DurationString, Error, Result, Unit};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// The format of clock notation, as the `expected` of [`Error::Format`].
+const CLOCK_FORMAT: &str = "clock notation such as `01:30:00`, `90:00` or `2d 03:00:00.250`";
+
+/// Parses `[<days>d ][HH:]MM:SS[.fff]`, the leading field may exceed its usual range.
+///
+/// The digits and the day prefix follow the options of `parser`.
+pub(crate) fn parse_clock(parser: &DurationParser, input: &str) -> Result<DurationString> {
+    let end = input.trim_end().len();
+    let start = input.len() - input.trim_start().len();
+    let clock_start = input[..end]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(start, |(i, c)| i + c.len_utf8());
+
+    let mut total: u128 = 0;
+    let days = input[start..clock_start].trim_end();
+    if !days.is_empty() {
+        total = days_in_nano(parser, days, start)?;
+    }
+
+    let mut fields: Vec<(&str, Range<usize>)> = Vec::new();
+    let mut field_start = clock_start;
+    for field in input[clock_start..end].split(':') {
+        fields.push((field, field_start..field_start + field.len()));
+        field_start += field.len() + 1;
+    }
+    let units: &[u128] = match fields.len() {
+        2 => &[MINUTE_IN_NANO, SECOND_IN_NANO],
+        3 => &[HOUR_IN_NANO, MINUTE_IN_NANO, SECOND_IN_NANO],
+        _ => {
+            return Err(Error::Format {
+                span: clock_start..end,
+                token: input[clock_start..end].to_string(),
+                expected: CLOCK_FORMAT,
+            })
+        }
+    };
+
+    for (i, ((field, span), unit_in_nano)) in fields.iter().zip(units).enumerate() {
+        if field.is_empty() {
+            return Err(Error::MissingQuantity {
+                span: span.start..span.start,
+            });
+        }
+        let field = ascii_quantity(parser, field, span)?;
+        // Only the seconds may have a fraction, and only the leading field
+        // may have other than two digits
+        let (integer, fraction) = match field.split_once('.') {
+            Some((integer, fraction)) if i == units.len() - 1 => (integer, Some(fraction)),
+            _ => (field.as_str(), None),
+        };
+        let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        let valid = digits(integer)
+            && fraction.is_none_or(digits)
+            && (i == 0 || (integer.len() == 2 && integer < "60"));
+        if !valid {
+            return Err(Error::Format {
+                span: span.clone(),
+                token: input[span.clone()].to_string(),
+                expected: CLOCK_FORMAT,
+            });
+        }
+        let nanos = quantity_in_nano(&field, span.clone(), *unit_in_nano, CLOCK_FORMAT)?;
+        total = total.checked_add(nanos).ok_or(Error::Overflow)?;
+    }
+    duration_from_nanos(total).map(DurationString::new)
+}
+
+/// The `[<days>d]` prefix at `offset` of the input in nanoseconds, any
+/// spelling of [`Unit::Day`] the parser accepts is allowed.
+fn days_in_nano(parser: &DurationParser, days: &str, offset: usize) -> Result<u128> {
+    let format = || Error::Format {
+        span: offset..offset + days.len(),
+        token: days.to_string(),
+        expected: CLOCK_FORMAT,
+    };
+    let mut count = String::new();
+    let mut count_end = 0;
+    let mut unit_start = None;
+    for (i, c) in days.char_indices() {
+        if c.is_whitespace() {
+            // `days` is trimmed, so the whitespace is followed by more
+            if parser.strict {
+                let end = days[i..]
+                    .find(|c: char| !c.is_whitespace())
+                    .map_or(days.len(), |len| i + len);
+                return Err(Error::UnexpectedWhitespace {
+                    span: offset + i..offset + end,
+                });
+            }
+            continue;
+        }
+        if unit_start.is_some() {
+            continue;
+        }
+        let span = offset + i..offset + i + c.len_utf8();
+        match parser.quantity_char(c, &span)? {
+            Some('.') => return Err(format()),
+            Some(digit) => {
+                count.push(digit);
+                count_end = i + c.len_utf8();
+            }
+            None => unit_start = Some(i),
+        }
+    }
+    let unit = unit_start.map_or("", |start| &days[start..]);
+    if count.is_empty() || !parser.is_unit(unit, Unit::Day) {
+        return Err(format());
+    }
+    quantity_in_nano(
+        &count,
+        offset..offset + count_end,
+        DAY_IN_NANO,
+        CLOCK_FORMAT,
+    )
+}
+
+/// `field` at `span` of the input with its digits in ASCII, see
+/// [`DurationParser::unicode_digits`].
+fn ascii_quantity(parser: &DurationParser, field: &str, span: &Range<usize>) -> Result<String> {
+    field
+        .char_indices()
+        .map(|(i, c)| {
+            let at = span.start + i..span.start + i + c.len_utf8();
+            parser.quantity_char(c, &at)?.ok_or_else(|| Error::Format {
+                span: span.clone(),
+                token: field.to_string(),
+                expected: CLOCK_FORMAT,
+            })
+        })
+        .collect()
+}
+
+impl DurationString {
+    /// Formats in clock notation, e.g. `01:30:00`, `00:00:01.250` or `2d 03:00:00`.
+    ///
+    /// Fractional seconds are written in groups of three digits, and a day
+    /// prefix is added from 24 hours. The result parses back with `FromStr`.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let d: DurationString = "90:00".parse().unwrap();
+    /// assert_eq!(d, Duration::from_secs(5400));
+    /// assert_eq!(d.to_clock_string(), "01:30:00");
+    /// ```
+    #[must_use]
+    pub fn to_clock_string(&self) -> String {
+        let ns = self.as_nanos();
+        let mut s = String::new();
+        // Writing to a `String` never fails
+        let days = ns / DAY_IN_NANO;
+        if days > 0 {
+            let _ = write!(s, "{days}d ");
+        }
+        let _ = write!(
+            s,
+            "{:02}:{:02}:{:02}",
+            ns % DAY_IN_NANO / HOUR_IN_NANO,
+            ns % HOUR_IN_NANO / MINUTE_IN_NANO,
+            ns % MINUTE_IN_NANO / SECOND_IN_NANO
+        );
+        let nanos = ns % SECOND_IN_NANO;
+        if nanos > 0 {
+            let digits = format!("{nanos:09}");
+            let significant = digits.trim_end_matches('0').len();
+            let _ = write!(s, ".{}", &digits[..significant.div_ceil(3) * 3]);
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn clock(input: &str) -> Result<Duration> {
+        parse_clock(&DurationParser::new(), input).map(Duration::from)
+    }
+
+    #[test]
+    fn test_parse_clock() {
+        assert_eq!(clock("01:30:00"), Ok(Duration::from_secs(5400)));
+        assert_eq!(clock("90:00"), Ok(Duration::from_secs(5400)));
+        assert_eq!(clock("00:00:01.250"), Ok(Duration::from_millis(1250)));
+        assert_eq!(clock("1:02:03"), Ok(Duration::from_secs(3723)));
+        assert_eq!(clock("36:00:00"), Ok(Duration::from_secs(129_600)));
+        assert_eq!(clock("00:00.000000001"), Ok(Duration::from_nanos(1)));
+        assert_eq!(clock(" 00:01 "), Ok(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_parse_clock_days() {
+        assert_eq!(clock("2d 03:00:00"), Ok(Duration::from_secs(183_600)));
+        assert_eq!(clock("1d  00:00:01"), Ok(Duration::from_secs(86_401)));
+    }
+
+    #[test]
+    fn test_parse_clock_options() {
+        let clock_with =
+            |parser: DurationParser, input| parse_clock(&parser, input).map(Duration::from);
+        assert_eq!(clock("1 d 00:01"), Ok(Duration::from_secs(86_401)));
+        assert_eq!(
+            clock_with(DurationParser::new().strict(true), "1 d 00:01"),
+            Err(Error::UnexpectedWhitespace { span: 1..2 })
+        );
+        assert_eq!(
+            clock_with(DurationParser::new().strict(true), "1d    00:01"),
+            Ok(Duration::from_secs(86_401))
+        );
+
+        assert_eq!(
+            clock("\u{661}:\u{660}\u{660}"),
+            Err(Error::NonAsciiDigit {
+                span: 0..2,
+                digit: '\u{661}',
+            })
+        );
+        let unicode = DurationParser::new().unicode_digits(true);
+        assert_eq!(
+            clock_with(unicode.clone(), "\u{661}:\u{660}\u{660}"),
+            Ok(Duration::from_secs(60))
+        );
+        assert_eq!(
+            unicode.parse("\u{661}:\u{660}\u{660}").map(Duration::from),
+            Ok(Duration::from_secs(60))
+        );
+        assert_eq!(
+            clock_with(unicode, "\u{662}d 01:00:00.\u{665}"),
+            Ok(Duration::from_millis(2 * 86_400_000 + 3_600_500))
+        );
+
+        assert!(clock("2 days 01:00").is_err());
+        assert_eq!(
+            clock_with(DurationParser::new().aliases(true), "2 days 01:00"),
+            Ok(Duration::from_secs(2 * 86_400 + 60))
+        );
+        assert_eq!(
+            clock_with(DurationParser::new().case_insensitive(true), "2D 01:00"),
+            Ok(Duration::from_secs(2 * 86_400 + 60))
+        );
+    }
+
+    #[test]
+    fn test_parse_clock_error_display() {
+        assert_eq!(
+            clock("01:60:00").unwrap_err().to_string(),
+            "invalid `60` at 3..5, must be clock notation such as `01:30:00`, `90:00` or `2d 03:00:00.250`"
+        );
+        assert_eq!(
+            clock("2h 03:00").unwrap_err().to_string(),
+            "invalid `2h` at 0..2, must be clock notation such as `01:30:00`, `90:00` or `2d 03:00:00.250`"
+        );
+    }
+
+    #[test]
+    fn test_parse_clock_errors() {
+        assert_eq!(
+            clock("01:60:00"),
+            Err(Error::Format {
+                span: 3..5,
+                token: String::from("60"),
+                expected: CLOCK_FORMAT,
+            })
+        );
+        assert_eq!(
+            clock("01:5:00"),
+            Err(Error::Format {
+                span: 3..4,
+                token: String::from("5"),
+                expected: CLOCK_FORMAT,
+            })
+        );
+        assert_eq!(
+            clock("01.5:00"),
+            Err(Error::Format {
+                span: 0..4,
+                token: String::from("01.5"),
+                expected: CLOCK_FORMAT,
+            })
+        );
+        assert_eq!(
+            clock("1:02:03:04"),
+            Err(Error::Format {
+                span: 0..10,
+                token: String::from("1:02:03:04"),
+                expected: CLOCK_FORMAT,
+            })
+        );
+        assert_eq!(clock(":30"), Err(Error::MissingQuantity { span: 0..0 }));
+        assert_eq!(
+            clock("2h 03:00"),
+            Err(Error::Format {
+                span: 0..2,
+                token: String::from("2h"),
+                expected: CLOCK_FORMAT,
+            })
+        );
+        assert_eq!(
+            clock("00:00.0000000001"),
+            Err(Error::Precision { span: 3..16 })
+        );
+    }
+
+    #[test]
+    fn test_from_str_clock() {
+        assert_eq!(
+            "01:30:00".parse::<DurationString>(),
+            Ok(DurationString::new(Duration::from_secs(5400)))
+        );
+        assert_eq!(
+            "-00:30".parse::<crate::SignedDurationString>(),
+            Ok(crate::SignedDurationString::new(
+                true,
+                Duration::from_secs(30)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_to_clock_string() {
+        let clock_string = |duration| DurationString::new(duration).to_clock_string();
+        assert_eq!(clock_string(Duration::ZERO), "00:00:00");
+        assert_eq!(clock_string(Duration::from_secs(5400)), "01:30:00");
+        assert_eq!(clock_string(Duration::from_millis(1250)), "00:00:01.250");
+        assert_eq!(clock_string(Duration::from_micros(1)), "00:00:00.000001");
+        assert_eq!(clock_string(Duration::new(1, 100)), "00:00:01.000000100");
+        assert_eq!(clock_string(Duration::from_secs(183_600)), "2d 03:00:00");
+    }
+
+    #[test]
+    fn test_clock_round_trip() {
+        let duration = Duration::new(200_000, 1_001);
+        let s = DurationString::new(duration).to_clock_string();
+        assert_eq!(clock(&s), Ok(duration));
+    }
+}
@@ -0,0 +1,158 @@
+//! Units defined by the caller, such as `epoch` or `slot`.
+
+use crate::unit::UNITS_WITH_MONTHS;
+use std::time::Duration;
+
+/// A unit registered with [`CustomUnits::unit`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct CustomUnit {
+    pub(crate) symbols: Vec<String>,
+    pub(crate) nanos: u128,
+}
+
+impl CustomUnit {
+    /// The symbol the unit is written with.
+    pub(crate) fn symbol(&self) -> &str {
+        &self.symbols[0]
+    }
+}
+
+/// Extra units for a [`DurationParser`](crate::DurationParser) or
+/// [`DurationFormatter`](crate::DurationFormatter), on top of the built-in
+/// `ns|us|ms|s|m|h|d|w|y`.
+///
+/// A symbol cannot be that of a built-in unit or of another custom unit, so
+/// every formatted duration parses back into the same value.
+///
+/// ```rust
+/// use duration_string::{CustomUnits, DurationFormatter, DurationParser};
+/// use std::time::Duration;
+///
+/// let units = CustomUnits::new()
+///     .unit(&["epoch", "epochs"], Duration::from_secs(384))
+///     .unit(&["slot", "slots"], Duration::from_secs(12));
+/// let parser = DurationParser::new().custom_units(units.clone());
+/// let d = parser.parse("3 epochs 2 slots").unwrap();
+/// assert_eq!(d, Duration::from_secs(1176));
+///
+/// let formatter = DurationFormatter::new().separator(" ").custom_units(units);
+/// assert_eq!(formatter.format(d), "3epoch 2slot");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CustomUnits {
+    /// Ordered from the longest to the shortest unit.
+    units: Vec<CustomUnit>,
+}
+
+impl CustomUnits {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a unit of `length` written as any of `symbols`, the first
+    /// one is used for formatting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `symbols` is empty or `length` is zero. Also panics if a
+    /// symbol is empty, contains a numeric character such as `3`, `²` or `٣`,
+    /// `.`, `:` or whitespace, or is already used by a built-in unit or alias,
+    /// ignoring ASCII case, or by another custom unit.
+    #[must_use]
+    pub fn unit(mut self, symbols: &[&str], length: Duration) -> Self {
+        assert!(!symbols.is_empty(), "a custom unit needs a symbol");
+        assert!(!length.is_zero(), "a custom unit cannot be zero");
+        for symbol in symbols {
+            assert!(
+                !symbol.is_empty()
+                    && !symbol.contains(|c: char| {
+                        c.is_numeric() || c == '.' || c == ':' || c.is_whitespace()
+                    }),
+                "invalid custom unit symbol {:?}",
+                symbol
+            );
+            assert!(
+                !self.is_taken(symbol),
+                "custom unit symbol {:?} is already used",
+                symbol
+            );
+        }
+        let unit = CustomUnit {
+            symbols: symbols.iter().map(|symbol| (*symbol).to_string()).collect(),
+            nanos: length.as_nanos(),
+        };
+        // After the units of the same length, which were registered first
+        let at = self
+            .units
+            .partition_point(|other| other.nanos >= unit.nanos);
+        self.units.insert(at, unit);
+        self
+    }
+
+    /// Whether `symbol` would be parsed as a unit already.
+    fn is_taken(&self, symbol: &str) -> bool {
+        let builtin = UNITS_WITH_MONTHS
+            .iter()
+            .flat_map(|unit| unit.symbols().iter().chain(unit.aliases()))
+            .any(|other| other.eq_ignore_ascii_case(symbol));
+        builtin
+            || self
+                .units
+                .iter()
+                .flat_map(|unit| &unit.symbols)
+                .any(|other| other.eq_ignore_ascii_case(symbol))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    pub(crate) fn units(&self) -> &[CustomUnit] {
+        &self.units
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_order() {
+        let units = CustomUnits::new()
+            .unit(&["tick"], Duration::from_millis(50))
+            .unit(&["epoch"], Duration::from_secs(384))
+            .unit(&["slot"], Duration::from_secs(12))
+            .unit(&["round"], Duration::from_secs(12));
+        let symbols: Vec<&str> = units.units().iter().map(CustomUnit::symbol).collect();
+        assert_eq!(symbols, ["epoch", "slot", "round", "tick"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "a custom unit cannot be zero")]
+    fn test_unit_zero() {
+        let _ = CustomUnits::new().unit(&["never"], Duration::ZERO);
+    }
+
+    #[test]
+    fn test_unit_invalid_symbol() {
+        for symbol in ["", "2x", "m\u{b2}", "x\u{663}", "x.y", "x:y", "epoch s"] {
+            let result = std::panic::catch_unwind(|| {
+                CustomUnits::new().unit(&["tick", symbol], Duration::from_secs(1))
+            });
+            assert!(result.is_err(), "{:?} was accepted", symbol);
+        }
+    }
+
+    #[test]
+    fn test_unit_taken_symbol() {
+        for symbol in ["m", "mo", "\u{b5}s", "H", "minutes", "Slot"] {
+            let result = std::panic::catch_unwind(|| {
+                CustomUnits::new()
+                    .unit(&["slot"], Duration::from_secs(12))
+                    .unit(&[symbol], Duration::from_secs(1))
+            });
+            assert!(result.is_err(), "{:?} was accepted", symbol);
+        }
+    }
+}
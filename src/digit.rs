@@ -0,0 +1,115 @@
+/// The zero of every run of Unicode decimal digits (general category `Nd`),
+/// each run is followed by the digits one through nine.
+const DECIMAL_ZEROS: [char; 77] = [
+    '\u{0030}',
+    '\u{0660}',
+    '\u{06f0}',
+    '\u{07c0}',
+    '\u{0966}',
+    '\u{09e6}',
+    '\u{0a66}',
+    '\u{0ae6}',
+    '\u{0b66}',
+    '\u{0be6}',
+    '\u{0c66}',
+    '\u{0ce6}',
+    '\u{0d66}',
+    '\u{0de6}',
+    '\u{0e50}',
+    '\u{0ed0}',
+    '\u{0f20}',
+    '\u{1040}',
+    '\u{1090}',
+    '\u{17e0}',
+    '\u{1810}',
+    '\u{1946}',
+    '\u{19d0}',
+    '\u{1a80}',
+    '\u{1a90}',
+    '\u{1b50}',
+    '\u{1bb0}',
+    '\u{1c40}',
+    '\u{1c50}',
+    '\u{a620}',
+    '\u{a8d0}',
+    '\u{a900}',
+    '\u{a9d0}',
+    '\u{a9f0}',
+    '\u{aa50}',
+    '\u{abf0}',
+    '\u{ff10}',
+    '\u{104a0}',
+    '\u{10d30}',
+    '\u{10d40}',
+    '\u{11066}',
+    '\u{110f0}',
+    '\u{11136}',
+    '\u{111d0}',
+    '\u{112f0}',
+    '\u{11450}',
+    '\u{114d0}',
+    '\u{11650}',
+    '\u{116c0}',
+    '\u{116d0}',
+    '\u{116da}',
+    '\u{11730}',
+    '\u{118e0}',
+    '\u{11950}',
+    '\u{11bf0}',
+    '\u{11c50}',
+    '\u{11d50}',
+    '\u{11da0}',
+    '\u{11de0}',
+    '\u{11f50}',
+    '\u{16130}',
+    '\u{16a60}',
+    '\u{16ac0}',
+    '\u{16b50}',
+    '\u{16d70}',
+    '\u{1ccf0}',
+    '\u{1d7ce}',
+    '\u{1d7d8}',
+    '\u{1d7e2}',
+    '\u{1d7ec}',
+    '\u{1d7f6}',
+    '\u{1e140}',
+    '\u{1e2f0}',
+    '\u{1e4f0}',
+    '\u{1e5f1}',
+    '\u{1e950}',
+    '\u{1fbf0}',
+];
+
+/// The value of a Unicode decimal digit such as `٣` or `３`, ASCII included.
+pub(crate) fn decimal_value(c: char) -> Option<u32> {
+    let run = DECIMAL_ZEROS
+        .partition_point(|zero| *zero <= c)
+        .checked_sub(1)?;
+    let value = u32::from(c) - u32::from(DECIMAL_ZEROS[run]);
+    if value < 10 {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_value() {
+        assert_eq!(decimal_value('7'), Some(7));
+        assert_eq!(decimal_value('\u{0663}'), Some(3));
+        assert_eq!(decimal_value('\u{ff19}'), Some(9));
+        assert_eq!(decimal_value('\u{1d7ff}'), Some(9));
+    }
+
+    #[test]
+    fn test_decimal_value_not_decimal() {
+        assert_eq!(decimal_value('a'), None);
+        assert_eq!(decimal_value('/'), None);
+        assert_eq!(decimal_value('\u{00b2}'), None);
+        assert_eq!(decimal_value('\u{2163}'), None);
+    }
+}
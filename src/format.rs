@@ -0,0 +1,663 @@
+use crate::unit::UNITS_WITH_MONTHS;
+use crate::{CustomUnits, DurationString, Unit, UnitSystem};
+use std::fmt;
+use std::io;
+
+/// How the part of a duration that is too small to be displayed is handled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum RoundingMode {
+    /// Drop the remainder, e.g. `1.9s` becomes `1s`.
+    #[default]
+    Floor,
+    /// Round half up to the nearest value, e.g. `1.5s` becomes `2s`.
+    Nearest,
+    /// Round any remainder up, e.g. `1.1s` becomes `2s`.
+    Ceil,
+}
+
+impl RoundingMode {
+    /// Whether a `remainder` of `unit_in_nano` should be rounded up to a whole unit.
+    pub(crate) fn rounds_up(self, remainder: u128, unit_in_nano: u128) -> bool {
+        match self {
+            Self::Floor => false,
+            Self::Nearest => remainder * 2 >= unit_in_nano,
+            Self::Ceil => remainder > 0,
+        }
+    }
+}
+
+/// Configurable rendering of a [`DurationString`].
+///
+/// The default formatter produces the same output as the `Display` impl, e.g.
+/// `1h30m15s`.
+///
+/// ```rust
+/// use duration_string::{DurationFormatter, DurationString, RoundingMode, Unit};
+/// use std::time::Duration;
+///
+/// let formatter = DurationFormatter::new()
+///     .separator(" ")
+///     .largest_unit(Unit::Day)
+///     .max_components(2)
+///     .rounding(RoundingMode::Nearest);
+/// let d = DurationString::new(Duration::from_secs(5_415));
+/// assert_eq!(formatter.format(d), "1h 30m");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DurationFormatter {
+    separator: String,
+    largest_unit: Unit,
+    smallest_unit: Unit,
+    max_components: Option<usize>,
+    rounding: RoundingMode,
+    micro_sign: bool,
+    unit_system: UnitSystem,
+    custom_units: CustomUnits,
+}
+
+impl Default for DurationFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationFormatter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            separator: String::new(),
+            largest_unit: Unit::Year,
+            smallest_unit: Unit::Nanosecond,
+            max_components: None,
+            rounding: RoundingMode::Floor,
+            micro_sign: false,
+            unit_system: UnitSystem::new(),
+            custom_units: CustomUnits::new(),
+        }
+    }
+
+    /// Text placed between components, e.g. `" "` for `1h 30m`. Empty by default.
+    #[must_use]
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// The largest unit used, e.g. [`Unit::Day`] renders one year as `365d5h48m46s`.
+    ///
+    /// [`Unit::Month`] stands for weeks unless the unit system has months.
+    #[must_use]
+    pub fn largest_unit(mut self, unit: Unit) -> Self {
+        self.largest_unit = unit;
+        self
+    }
+
+    /// The smallest unit used, anything below it is rounded away.
+    ///
+    /// If it is larger than the largest unit, the largest unit is used instead.
+    /// [`Unit::Month`] stands for years unless the unit system has months.
+    #[must_use]
+    pub fn smallest_unit(mut self, unit: Unit) -> Self {
+        self.smallest_unit = unit;
+        self
+    }
+
+    /// The maximum number of units, counted from the largest non-zero one.
+    ///
+    /// Units in between are counted even when they are zero, so `1h0m5s` with
+    /// two components is rendered as `1h`.
+    #[must_use]
+    pub fn max_components(mut self, max_components: usize) -> Self {
+        self.max_components = Some(max_components.max(1));
+        self
+    }
+
+    /// How the remainder below the last rendered unit is handled.
+    #[must_use]
+    pub fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Write microseconds with the micro sign as `µs`, like Go and Prometheus do,
+    /// rather than `us`. Both parse back.
+    #[must_use]
+    pub fn micro_sign(mut self, micro_sign: bool) -> Self {
+        self.micro_sign = micro_sign;
+        self
+    }
+
+    /// The length of a year and whether months are written, see [`UnitSystem`].
+    #[must_use]
+    pub fn unit_system(mut self, unit_system: UnitSystem) -> Self {
+        self.unit_system = unit_system;
+        self
+    }
+
+    /// Also write the units of `custom_units` that fit between the largest and
+    /// smallest unit, before any built-in unit of the same length.
+    #[must_use]
+    pub fn custom_units(mut self, custom_units: CustomUnits) -> Self {
+        self.custom_units = custom_units;
+        self
+    }
+
+    fn symbol(&self, unit: Unit) -> &'static str {
+        if self.micro_sign && unit == Unit::Microsecond {
+            "\u{b5}s"
+        } else {
+            unit.symbol()
+        }
+    }
+
+    /// Renders `duration` with the configured options.
+    #[must_use]
+    pub fn format(&self, duration: DurationString) -> String {
+        let mut s = String::new();
+        // Writing to a `String` never fails
+        let _ = self.write(duration, &mut s);
+        s
+    }
+
+    /// Writes `duration` into `out` with the configured options, without
+    /// allocating unless there are [`DurationFormatter::custom_units`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `out` fails.
+    pub fn write<W: fmt::Write + ?Sized>(
+        &self,
+        duration: DurationString,
+        out: &mut W,
+    ) -> fmt::Result {
+        // Custom units are only looked at when there are any, so the default
+        // formatter does not allocate
+        let builtin = self
+            .custom_units
+            .is_empty()
+            .then(|| self.components(duration))
+            .into_iter()
+            .flatten()
+            .map(|(count, unit)| (count, self.symbol(unit)));
+        let custom = (!self.custom_units.is_empty())
+            .then(|| self.custom_components(duration))
+            .into_iter()
+            .flatten();
+        let mut separator = "";
+        let mut empty = true;
+        for (count, symbol) in builtin.chain(custom) {
+            write!(out, "{separator}{count}{symbol}")?;
+            separator = &self.separator;
+            empty = false;
+        }
+        if empty {
+            write!(out, "0{}", self.symbol(self.zero_unit()))?;
+        }
+        Ok(())
+    }
+
+    /// Writes `duration` into `out` with the configured options, see
+    /// [`DurationFormatter::write`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `out` fails.
+    pub fn write_io<W: io::Write + ?Sized>(
+        &self,
+        duration: DurationString,
+        out: &mut W,
+    ) -> io::Result<()> {
+        write!(
+            out,
+            "{}",
+            Formatted {
+                formatter: self,
+                duration,
+            }
+        )
+    }
+
+    /// Renders `duration` into `buf` with the configured options, without
+    /// allocating.
+    ///
+    /// ```rust
+    /// use duration_string::{DurationFormatter, DurationString};
+    /// use std::time::Duration;
+    ///
+    /// let mut buf = [0; 64];
+    /// let d = DurationString::new(Duration::from_secs(5415));
+    /// let formatter = DurationFormatter::new().separator(" ");
+    /// assert_eq!(formatter.format_into(d, &mut buf), Ok("1h 30m 15s"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `buf` is too small.
+    pub fn format_into<'a>(
+        &self,
+        duration: DurationString,
+        buf: &'a mut [u8],
+    ) -> Result<&'a str, fmt::Error> {
+        let mut writer = SliceWriter { buf, len: 0 };
+        self.write(duration, &mut writer)?;
+        let SliceWriter { buf, len } = writer;
+        // Only whole `str`s were copied in, so this is always UTF-8
+        std::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)
+    }
+
+    /// The built-in units between the largest and smallest unit, and the
+    /// index of the first one in the unit system.
+    fn units(&self) -> (usize, &'static [Unit]) {
+        let smallest_unit = self.smallest_unit.min(self.largest_unit);
+        let all = self.unit_system.units();
+        // Months may be missing from the system, the fallbacks are never used
+        // as it always has seconds
+        let first = all
+            .iter()
+            .position(|unit| *unit <= self.largest_unit)
+            .unwrap_or(0);
+        let end = all
+            .iter()
+            .rposition(|unit| *unit >= smallest_unit)
+            .unwrap_or(all.len() - 1)
+            .max(first);
+        (first, &all[first..=end])
+    }
+
+    /// Splits `ns` into `counts` of the units of `lengths`, from largest to
+    /// smallest, after rounding. Both slices have the same length.
+    fn split(&self, ns: u128, lengths: &[u128], counts: &mut [u128]) {
+        let leading = lengths
+            .iter()
+            .position(|length| ns >= *length)
+            .unwrap_or(lengths.len() - 1);
+        let last = self.max_components.map_or(lengths.len() - 1, |max| {
+            (leading + max - 1).min(lengths.len() - 1)
+        });
+
+        // The counts past `last` stay zero
+        let mut remainder = ns;
+        for (count, length) in counts.iter_mut().zip(&lengths[..=last]) {
+            *count = remainder / length;
+            remainder %= length;
+        }
+        if self.rounding.rounds_up(remainder, lengths[last]) {
+            counts[last] += 1;
+            // Carry into larger units as long as they are whole multiples,
+            // e.g. `60s` becomes `1m` but `53w` stays as is.
+            for i in (1..=last).rev() {
+                let (larger, smaller) = (lengths[i - 1], lengths[i]);
+                if !larger.is_multiple_of(smaller) || counts[i] != larger / smaller {
+                    break;
+                }
+                counts[i] = 0;
+                counts[i - 1] += 1;
+            }
+        }
+    }
+
+    /// The non-zero `(count, unit)` components of `duration` from largest to
+    /// smallest, after rounding. Custom units are not used.
+    pub(crate) fn components(
+        &self,
+        duration: DurationString,
+    ) -> impl Iterator<Item = (u128, Unit)> {
+        let (first, units) = self.units();
+        let mut lengths = [0u128; UNITS_WITH_MONTHS.len()];
+        for (length, unit) in lengths.iter_mut().zip(units) {
+            *length = self.unit_system.unit_in_nano(*unit);
+        }
+        let mut counts = [0u128; UNITS_WITH_MONTHS.len()];
+        self.split(
+            duration.as_nanos(),
+            &lengths[..units.len()],
+            &mut counts[..units.len()],
+        );
+
+        // An owned iterator, arrays only iterate by value with `into_iter` from edition 2021
+        IntoIterator::into_iter(counts)
+            .zip(self.unit_system.units().iter().copied().skip(first))
+            .filter(|(count, _)| *count > 0)
+    }
+
+    /// The non-zero `(count, symbol)` components of `duration` like
+    /// [`DurationFormatter::components`], including the custom units that fit
+    /// between the largest and smallest unit.
+    fn custom_components(&self, duration: DurationString) -> Vec<(u128, &str)> {
+        let (_, units) = self.units();
+        let system = self.unit_system;
+        let longest = system.unit_in_nano(units[0]);
+        let shortest = system.unit_in_nano(units[units.len() - 1]);
+        let mut custom = self
+            .custom_units
+            .units()
+            .iter()
+            .filter(|unit| (shortest..=longest).contains(&unit.nanos))
+            .peekable();
+        // Merged by length, a custom unit goes before a built-in one of the same length
+        let mut merged: Vec<(&str, u128)> = Vec::new();
+        for unit in units {
+            let length = system.unit_in_nano(*unit);
+            while let Some(c) = custom.next_if(|c| c.nanos >= length) {
+                merged.push((c.symbol(), c.nanos));
+            }
+            merged.push((self.symbol(*unit), length));
+        }
+
+        let lengths: Vec<u128> = merged.iter().map(|(_, length)| *length).collect();
+        let mut counts = vec![0u128; merged.len()];
+        self.split(duration.as_nanos(), &lengths, &mut counts);
+        counts
+            .into_iter()
+            .zip(merged)
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, (symbol, _))| (count, symbol))
+            .collect()
+    }
+
+    /// The unit zero is written in, seconds unless they are out of range.
+    pub(crate) fn zero_unit(&self) -> Unit {
+        // One of the units actually written, as months may be disabled
+        let (_, units) = self.units();
+        Unit::Second.clamp(units[units.len() - 1], units[0])
+    }
+}
+
+/// A `duration` rendered by `formatter` through `Display`.
+struct Formatted<'a> {
+    formatter: &'a DurationFormatter,
+    duration: DurationString,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter.write(self.duration, f)
+    }
+}
+
+/// A `fmt::Write` into a byte slice, which fails once the slice is full.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use crate::{DurationParser, YearLength};
+    use std::time::Duration;
+
+    fn format(formatter: &DurationFormatter, duration: Duration) -> String {
+        formatter.format(DurationString::new(duration))
+    }
+
+    #[test]
+    fn test_format_default() {
+        let formatter = DurationFormatter::new();
+        assert_eq!(format(&formatter, Duration::from_secs(5415)), "1h30m15s");
+        assert_eq!(format(&formatter, Duration::ZERO), "0s");
+    }
+
+    #[test]
+    fn test_format_separator() {
+        let formatter = DurationFormatter::new().separator(" ");
+        assert_eq!(format(&formatter, Duration::from_secs(5415)), "1h 30m 15s");
+    }
+
+    #[test]
+    fn test_format_largest_unit() {
+        let formatter = DurationFormatter::new().largest_unit(Unit::Day);
+        assert_eq!(
+            format(&formatter, Duration::from_secs(31_556_926)),
+            "365d5h48m46s"
+        );
+        let formatter = DurationFormatter::new().largest_unit(Unit::Second);
+        assert_eq!(format(&formatter, Duration::from_secs(5415)), "5415s");
+    }
+
+    #[test]
+    fn test_format_smallest_unit() {
+        let formatter = DurationFormatter::new().smallest_unit(Unit::Millisecond);
+        assert_eq!(format(&formatter, Duration::new(1, 2_345_678)), "1s2ms");
+        assert_eq!(format(&formatter, Duration::from_micros(999)), "0s");
+    }
+
+    #[test]
+    fn test_format_smallest_unit_above_largest() {
+        let formatter = DurationFormatter::new()
+            .largest_unit(Unit::Minute)
+            .smallest_unit(Unit::Hour);
+        assert_eq!(format(&formatter, Duration::from_secs(5415)), "90m");
+        assert_eq!(format(&formatter, Duration::ZERO), "0m");
+    }
+
+    #[test]
+    fn test_format_max_components() {
+        let formatter = DurationFormatter::new().max_components(2);
+        assert_eq!(format(&formatter, Duration::from_secs(5415)), "1h30m");
+        assert_eq!(format(&formatter, Duration::from_secs(3605)), "1h");
+        assert_eq!(format(&formatter, Duration::new(5, 250)), "5s");
+    }
+
+    #[test]
+    fn test_format_rounding() {
+        let duration = Duration::from_millis(1500);
+        let formatter = DurationFormatter::new().smallest_unit(Unit::Second);
+        assert_eq!(format(&formatter, duration), "1s");
+        let formatter = formatter.rounding(RoundingMode::Nearest);
+        assert_eq!(format(&formatter, duration), "2s");
+        assert_eq!(format(&formatter, Duration::from_millis(1499)), "1s");
+        let formatter = formatter.rounding(RoundingMode::Ceil);
+        assert_eq!(format(&formatter, Duration::from_millis(1001)), "2s");
+        assert_eq!(format(&formatter, Duration::from_millis(1000)), "1s");
+    }
+
+    #[test]
+    fn test_format_rounding_carry() {
+        let formatter = DurationFormatter::new()
+            .max_components(1)
+            .rounding(RoundingMode::Nearest);
+        assert_eq!(format(&formatter, Duration::from_millis(59_900)), "1m");
+        assert_eq!(
+            format(&formatter, Duration::new(604_799, 600_000_000)),
+            "1w"
+        );
+        let formatter = formatter.max_components(3).separator(" ");
+        assert_eq!(format(&formatter, Duration::new(86_399, 600_000_000)), "1d");
+    }
+
+    #[test]
+    fn test_format_rounding_carry_stops_at_largest_unit() {
+        let formatter = DurationFormatter::new()
+            .largest_unit(Unit::Hour)
+            .smallest_unit(Unit::Minute)
+            .rounding(RoundingMode::Ceil);
+        assert_eq!(format(&formatter, Duration::from_secs(86_399)), "24h");
+    }
+
+    #[test]
+    fn test_format_micro_sign() {
+        let duration = Duration::from_nanos(1_002_003);
+        assert_eq!(format(&DurationFormatter::new(), duration), "1ms2us3ns");
+        let formatter = DurationFormatter::new().micro_sign(true);
+        let s = format(&formatter, duration);
+        assert_eq!(s, "1ms2\u{b5}s3ns");
+        assert_eq!(
+            s.parse::<DurationString>(),
+            Ok(DurationString::new(duration))
+        );
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let formatter = DurationFormatter::new().separator(" ");
+        let duration = Duration::new(31_556_927, 1_001_001);
+        let s = format(&formatter, duration);
+        assert_eq!(s, "1y 1s 1ms 1us 1ns");
+        assert_eq!(
+            s.parse::<DurationString>(),
+            Ok(DurationString::new(duration))
+        );
+    }
+
+    #[test]
+    fn test_format_unit_system() {
+        let system = UnitSystem::new().year(YearLength::Common);
+        let formatter = DurationFormatter::new().unit_system(system);
+        assert_eq!(format(&formatter, Duration::from_secs(31_536_000)), "1y");
+        assert_eq!(
+            format(&formatter, Duration::from_secs(31_556_926)),
+            "1y5h48m46s"
+        );
+
+        let formatter = formatter.unit_system(system.months(true)).separator(" ");
+        let duration = Duration::from_secs(31_536_000 + 2_628_000 + 86_400);
+        let s = format(&formatter, duration);
+        assert_eq!(s, "1y 1mo 1d");
+        let parser = crate::DurationParser::new().unit_system(system.months(true));
+        assert_eq!(parser.parse(&s), Ok(DurationString::new(duration)));
+    }
+
+    #[test]
+    fn test_format_unit_system_months() {
+        let formatter = DurationFormatter::new()
+            .unit_system(UnitSystem::new().months(true))
+            .largest_unit(Unit::Month)
+            .max_components(1)
+            .rounding(RoundingMode::Nearest);
+        // Tropical months do not add up to a year, so there is no carry
+        assert_eq!(format(&formatter, Duration::from_secs(31_556_926)), "12mo");
+        let formatter = formatter.unit_system(UnitSystem::new());
+        assert_eq!(format(&formatter, Duration::from_secs(1_209_600)), "2w");
+
+        // Without months, zero is not written in them either
+        let parser = DurationParser::new();
+        for formatter in [
+            DurationFormatter::new().smallest_unit(Unit::Month),
+            DurationFormatter::new()
+                .largest_unit(Unit::Month)
+                .smallest_unit(Unit::Month),
+        ] {
+            let s = format(&formatter, Duration::ZERO);
+            assert_eq!(
+                parser.parse(&s).map(Duration::from),
+                Ok(Duration::ZERO),
+                "{s}"
+            );
+        }
+        assert_eq!(
+            format(
+                &DurationFormatter::new().smallest_unit(Unit::Month),
+                Duration::ZERO
+            ),
+            "0y"
+        );
+    }
+
+    #[test]
+    fn test_format_custom_units() {
+        let units = CustomUnits::new()
+            .unit(&["epoch", "epochs"], Duration::from_secs(384))
+            .unit(&["slot", "slots"], Duration::from_secs(12))
+            .unit(&["fortnight"], Duration::from_secs(1_209_600))
+            .unit(&["century"], Duration::from_secs(3_155_692_600));
+        let formatter = DurationFormatter::new()
+            .separator(" ")
+            .custom_units(units.clone());
+        assert_eq!(
+            format(&formatter, Duration::from_secs(1176)),
+            "3epoch 2slot"
+        );
+        assert_eq!(
+            format(&formatter, Duration::from_secs(1_209_600 + 444)),
+            "1fortnight 1epoch 1m"
+        );
+        assert_eq!(format(&formatter, Duration::ZERO), "0s");
+
+        let formatter = formatter
+            .largest_unit(Unit::Minute)
+            .smallest_unit(Unit::Second);
+        // Epochs are longer than a minute, slots fit in between
+        assert_eq!(
+            format(&formatter, Duration::from_millis(1_176_500)),
+            "19m 3slot"
+        );
+
+        let formatter = DurationFormatter::new()
+            .custom_units(units)
+            .max_components(1)
+            .rounding(RoundingMode::Nearest);
+        assert_eq!(format(&formatter, Duration::from_secs(760)), "2epoch");
+    }
+
+    #[test]
+    fn test_format_custom_units_round_trip() {
+        let units = CustomUnits::new()
+            .unit(&["epoch", "epochs"], Duration::from_secs(384))
+            .unit(&["slot", "slots"], Duration::from_secs(12))
+            .unit(&["tick", "ticks"], Duration::from_millis(50))
+            .unit(&["beat"], Duration::from_millis(50));
+        let formatter = DurationFormatter::new().custom_units(units.clone());
+        let parser = DurationParser::new().custom_units(units);
+        for d in [
+            Duration::ZERO,
+            Duration::from_nanos(1),
+            Duration::from_millis(1_176_550),
+            Duration::from_secs(3600 + 384 + 12 + 1),
+            Duration::new(86_400 * 400 + 395, 50_000_123),
+            Duration::MAX,
+        ] {
+            let s = format(&formatter, d);
+            assert_eq!(parser.parse(&s).map(Duration::from), Ok(d), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_write() {
+        let formatter = DurationFormatter::new().separator(" ");
+        let mut s = String::from("took ");
+        formatter
+            .write(DurationString::new(Duration::from_secs(5415)), &mut s)
+            .unwrap();
+        assert_eq!(s, "took 1h 30m 15s");
+    }
+
+    #[test]
+    fn test_write_io() {
+        let mut out: Vec<u8> = Vec::new();
+        DurationFormatter::new()
+            .write_io(DurationString::new(Duration::ZERO), &mut out)
+            .unwrap();
+        assert_eq!(out, b"0s");
+    }
+
+    #[test]
+    fn test_format_into() {
+        let formatter = DurationFormatter::new();
+        let d = DurationString::new(Duration::from_secs(5415));
+        let mut buf = [0; 8];
+        assert_eq!(formatter.format_into(d, &mut buf), Ok("1h30m15s"));
+        let mut buf = [0; 7];
+        assert_eq!(formatter.format_into(d, &mut buf), Err(fmt::Error));
+    }
+
+    #[test]
+    fn test_format_into_longest() {
+        let mut buf = [0; 64];
+        let d = DurationString::new(Duration::MAX);
+        assert_eq!(d.format_into(&mut buf), Ok(d.to_string().as_str()));
+    }
+}
@@ -0,0 +1,484 @@
+//! Compatibility with Go's `time.ParseDuration` and `time.Duration.String()`.
+
+use crate::unit::{
+    HOUR_IN_NANO, MICROSECOND_IN_NANO, MILLISECOND_IN_NANO, MINUTE_IN_NANO, SECOND_IN_NANO,
+};
+use crate::{expected_units, parser, DurationString, Error, Result, SignedDurationString};
+use std::convert::TryFrom;
+
+/// The magnitude of Go's `math.MinInt64` nanoseconds, one more than the maximum.
+const GO_LIMIT: u64 = 1 << 63;
+
+/// The format of Go durations, as the `expected` of [`Error::Format`].
+const GO_FORMAT: &str = "a Go duration such as `1h30m` or `-1.5s`";
+
+/// Every unit Go accepts, as the `expected` of unit errors.
+const GO_UNITS: [&str; 8] = ["ns", "us", "\u{b5}s", "\u{3bc}s", "ms", "s", "m", "h"];
+
+fn go_unit(unit: &str) -> Option<u64> {
+    let unit = match unit {
+        "ns" => 1,
+        "us" | "\u{b5}s" | "\u{3bc}s" => MICROSECOND_IN_NANO,
+        "ms" => MILLISECOND_IN_NANO,
+        "s" => SECOND_IN_NANO,
+        "m" => MINUTE_IN_NANO,
+        "h" => HOUR_IN_NANO,
+        _ => return None,
+    };
+    // Every unit is at most an hour, which fits in a `u64`
+    u64::try_from(unit).ok()
+}
+
+/// Consumes the leading `[0-9]*` of `s` from `i`, `None` on overflow.
+fn leading_int(s: &[u8], i: &mut usize) -> Option<u64> {
+    let mut x: u64 = 0;
+    while let Some(c) = s.get(*i).filter(|c| c.is_ascii_digit()) {
+        if x > GO_LIMIT / 10 {
+            return None;
+        }
+        x = x * 10 + u64::from(c - b'0');
+        if x > GO_LIMIT {
+            return None;
+        }
+        *i += 1;
+    }
+    Some(x)
+}
+
+/// Consumes the leading `[0-9]*` of a fraction from `i`, dropping any digits
+/// beyond what fits like Go does. Returns the digits and their scale.
+fn leading_fraction(s: &[u8], i: &mut usize) -> (u64, f64) {
+    let mut x: u64 = 0;
+    let mut scale = 1.0;
+    let mut overflow = false;
+    while let Some(c) = s.get(*i).filter(|c| c.is_ascii_digit()) {
+        *i += 1;
+        if overflow {
+            continue;
+        }
+        if x > (GO_LIMIT - 1) / 10 {
+            overflow = true;
+            continue;
+        }
+        let y = x * 10 + u64::from(c - b'0');
+        if y > GO_LIMIT {
+            overflow = true;
+            continue;
+        }
+        x = y;
+        scale *= 10.0;
+    }
+    (x, scale)
+}
+
+/// Parses `[-+]?([0-9]*(\.[0-9]*)?[a-z]+)+` exactly like Go's `time.ParseDuration`.
+// Go computes fractions in `float64`, so must we to get the same nanoseconds
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn parse_go(input: &str) -> Result<SignedDurationString> {
+    let s = input.as_bytes();
+    let mut i = 0;
+    let mut negative = false;
+    if let Some(c @ (b'-' | b'+')) = s.first() {
+        negative = *c == b'-';
+        i += 1;
+    }
+    // Special case: a lone "0" is zero
+    if &s[i..] == b"0" {
+        return Ok(SignedDurationString::default());
+    }
+    if i == s.len() {
+        return Err(Error::Empty);
+    }
+    let mut total: u64 = 0;
+    while i < s.len() {
+        let start = i;
+        // The next character must be [0-9.]
+        if !(s[i] == b'.' || s[i].is_ascii_digit()) {
+            return Err(Error::MissingQuantity { span: i..i });
+        }
+        let mut value = leading_int(s, &mut i).ok_or(Error::Overflow)?;
+        let pre = i != start;
+        let mut fraction = (0, 1.0);
+        let mut post = false;
+        if s.get(i) == Some(&b'.') {
+            i += 1;
+            let fraction_start = i;
+            fraction = leading_fraction(s, &mut i);
+            post = i != fraction_start;
+        }
+        if !pre && !post {
+            return Err(Error::Format {
+                span: start..i,
+                token: input[start..i].to_string(),
+                expected: GO_FORMAT,
+            });
+        }
+
+        let unit_start = i;
+        while i < s.len() && !(s[i] == b'.' || s[i].is_ascii_digit()) {
+            i += 1;
+        }
+        if i == unit_start {
+            return Err(Error::MissingUnit {
+                span: i..i,
+                expected: expected_units(GO_UNITS),
+            });
+        }
+        let unit = &input[unit_start..i];
+        let unit_in_nano = go_unit(unit).ok_or_else(|| Error::UnknownUnit {
+            span: unit_start..i,
+            unit: unit.to_string(),
+            expected: expected_units(GO_UNITS),
+        })?;
+        if value > GO_LIMIT / unit_in_nano {
+            return Err(Error::Overflow);
+        }
+        value *= unit_in_nano;
+        let (fraction, scale) = fraction;
+        if fraction > 0 {
+            value += (fraction as f64 * (unit_in_nano as f64 / scale)) as u64;
+            if value > GO_LIMIT {
+                return Err(Error::Overflow);
+            }
+        }
+        // Both are at most `GO_LIMIT`, but their sum can exceed a `u64`
+        total = total.checked_add(value).ok_or(Error::Overflow)?;
+        if total > GO_LIMIT {
+            return Err(Error::Overflow);
+        }
+    }
+    if !negative && total > GO_LIMIT - 1 {
+        return Err(Error::Overflow);
+    }
+    let magnitude = parser::duration_from_nanos(u128::from(total))?;
+    Ok(SignedDurationString::new(negative, magnitude))
+}
+
+/// Formats `v / 10^precision` as the fraction `.123`, without trailing zeros
+/// or the decimal point when the fraction is zero. Also returns the integer part.
+fn go_fraction(v: u128, precision: u32) -> (String, u128) {
+    let scale = 10u128.pow(precision);
+    let fraction = v % scale;
+    if fraction == 0 {
+        return (String::new(), v / scale);
+    }
+    let digits = format!("{fraction:0width$}", width = precision as usize);
+    (format!(".{}", digits.trim_end_matches('0')), v / scale)
+}
+
+/// Formats like Go's `time.Duration.String()`, e.g. `1h2m3.5s` or `1.5ms`.
+fn go_string(negative: bool, nanos: u128) -> String {
+    let sign = if negative { "-" } else { "" };
+    if nanos == 0 {
+        return String::from("0s");
+    }
+    if nanos < SECOND_IN_NANO {
+        // Smaller units are used below a second, like `1.2ms`
+        let (unit, precision) = if nanos < MICROSECOND_IN_NANO {
+            ("ns", 0)
+        } else if nanos < MILLISECOND_IN_NANO {
+            ("\u{b5}s", 3)
+        } else {
+            ("ms", 6)
+        };
+        let (fraction, integer) = go_fraction(nanos, precision);
+        return format!("{sign}{integer}{fraction}{unit}");
+    }
+    let (fraction, secs) = go_fraction(nanos, 9);
+    let mut s = format!("{}{fraction}s", secs % 60);
+    let minutes = secs / 60;
+    if minutes > 0 {
+        s = format!("{}m{s}", minutes % 60);
+        let hours = minutes / 60;
+        if hours > 0 {
+            s = format!("{hours}h{s}");
+        }
+    }
+    format!("{sign}{s}")
+}
+
+impl DurationString {
+    /// Parses the grammar of Go's `time.ParseDuration`, such as `1h2m3.5s` or
+    /// `300ms`. Only `ns`, `us`, `µs`, `ms`, `s`, `m` and `h` are allowed and
+    /// the value must fit in Go's `time.Duration`.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let d = DurationString::parse_go("1h2m3.5s").unwrap();
+    /// assert_eq!(d, Duration::from_millis(3_723_500));
+    /// assert_eq!(d.to_go_string(), "1h2m3.5s");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] where Go would, and [`Error::Negative`] for a
+    /// negative duration, see [`SignedDurationString::parse_go`].
+    pub fn parse_go(input: &str) -> Result<Self> {
+        parse_go(input).and_then(DurationString::try_from)
+    }
+
+    /// Formats like Go's `time.Duration.String()`, e.g. `2h0m0s`, `1.5s` or `1µs`.
+    ///
+    /// Durations beyond the range of Go's `time.Duration` are formatted the same
+    /// way, but Go cannot parse them back.
+    #[must_use]
+    pub fn to_go_string(&self) -> String {
+        go_string(false, self.as_nanos())
+    }
+}
+
+impl SignedDurationString {
+    /// Parses the grammar of Go's `time.ParseDuration`, such as `-1.5h`,
+    /// see [`DurationString::parse_go`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] where Go would.
+    pub fn parse_go(input: &str) -> Result<Self> {
+        parse_go(input)
+    }
+
+    /// Formats like Go's `time.Duration.String()`, e.g. `-1h30m0s`.
+    #[must_use]
+    pub fn to_go_string(&self) -> String {
+        go_string(self.is_negative(), self.abs().as_nanos())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const MAX: Duration = Duration::new(9_223_372_036, 854_775_807);
+    const MIN: Duration = Duration::new(9_223_372_036, 854_775_808);
+
+    fn go(input: &str) -> SignedDurationString {
+        SignedDurationString::parse_go(input).unwrap()
+    }
+
+    fn positive(duration: Duration) -> SignedDurationString {
+        SignedDurationString::new(false, duration)
+    }
+
+    fn negative(duration: Duration) -> SignedDurationString {
+        SignedDurationString::new(true, duration)
+    }
+
+    // Cases from Go's `time.TestParseDuration`
+    #[test]
+    fn test_parse_go_simple() {
+        assert_eq!(go("0"), positive(Duration::ZERO));
+        assert_eq!(go("5s"), positive(Duration::from_secs(5)));
+        assert_eq!(go("30s"), positive(Duration::from_secs(30)));
+        assert_eq!(go("1478s"), positive(Duration::from_secs(1478)));
+        assert_eq!(go("-5s"), negative(Duration::from_secs(5)));
+        assert_eq!(go("+5s"), positive(Duration::from_secs(5)));
+        assert_eq!(go("-0"), positive(Duration::ZERO));
+        assert_eq!(go("+0"), positive(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_go_decimal() {
+        assert_eq!(go("5.0s"), positive(Duration::from_secs(5)));
+        assert_eq!(go("5.6s"), positive(Duration::from_millis(5600)));
+        assert_eq!(go("5.s"), positive(Duration::from_secs(5)));
+        assert_eq!(go(".5s"), positive(Duration::from_millis(500)));
+        assert_eq!(go("1.004s"), positive(Duration::from_millis(1004)));
+        assert_eq!(go("1.0040s"), positive(Duration::from_millis(1004)));
+        assert_eq!(go("100.00100s"), positive(Duration::from_millis(100_001)));
+    }
+
+    #[test]
+    fn test_parse_go_units() {
+        assert_eq!(go("10ns"), positive(Duration::from_nanos(10)));
+        assert_eq!(go("11us"), positive(Duration::from_micros(11)));
+        assert_eq!(go("12\u{b5}s"), positive(Duration::from_micros(12)));
+        assert_eq!(go("12\u{3bc}s"), positive(Duration::from_micros(12)));
+        assert_eq!(go("13ms"), positive(Duration::from_millis(13)));
+        assert_eq!(go("15m"), positive(Duration::from_secs(900)));
+        assert_eq!(go("16h"), positive(Duration::from_secs(57_600)));
+    }
+
+    #[test]
+    fn test_parse_go_composite() {
+        assert_eq!(go("3h30m"), positive(Duration::from_secs(12_600)));
+        assert_eq!(go("10.5s4m"), positive(Duration::from_millis(250_500)));
+        assert_eq!(go("-2m3.4s"), negative(Duration::from_millis(123_400)));
+        assert_eq!(
+            go("1h2m3s4ms5us6ns"),
+            positive(Duration::new(3723, 4_005_006))
+        );
+        assert_eq!(
+            go("39h9m14.425s"),
+            positive(Duration::from_millis(140_954_425))
+        );
+        // Go allows repeated units
+        assert_eq!(go("1s1s"), positive(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_parse_go_large() {
+        assert_eq!(
+            go("52763797000ns"),
+            positive(Duration::from_nanos(52_763_797_000))
+        );
+        assert_eq!(
+            go("0.3333333333333333333h"),
+            positive(Duration::from_secs(1200))
+        );
+        assert_eq!(
+            go("9007199254740993ns"),
+            positive(Duration::from_nanos(9_007_199_254_740_993))
+        );
+        assert_eq!(go("9223372036854775807ns"), positive(MAX));
+        assert_eq!(go("9223372036854775.807us"), positive(MAX));
+        assert_eq!(go("9223372036s854ms775us807ns"), positive(MAX));
+        assert_eq!(go("-9223372036854775808ns"), negative(MIN));
+        assert_eq!(go("-9223372036854775.808us"), negative(MIN));
+        assert_eq!(go("-9223372036s854ms775us808ns"), negative(MIN));
+        assert_eq!(go("-2562047h47m16.854775808s"), negative(MIN));
+        assert_eq!(
+            go("0.100000000000000000000h"),
+            positive(Duration::from_secs(360))
+        );
+        assert_eq!(
+            go("0.830103483285477580700h"),
+            positive(Duration::new(2988, 372_539_827))
+        );
+    }
+
+    #[test]
+    fn test_parse_go_invalid() {
+        for input in [
+            "", "3", "-", "s", ".", "-.", ".s", "+.s", "1d", "1 s", "1.5y",
+        ] {
+            SignedDurationString::parse_go(input)
+                .expect_err(&format!("parsing {input:?} should fail"));
+        }
+    }
+
+    #[test]
+    fn test_parse_go_errors() {
+        assert_eq!(SignedDurationString::parse_go("-"), Err(Error::Empty));
+        assert_eq!(
+            SignedDurationString::parse_go("3"),
+            Err(Error::MissingUnit {
+                span: 1..1,
+                expected: expected_units(GO_UNITS),
+            })
+        );
+        assert_eq!(
+            SignedDurationString::parse_go("1h1d"),
+            Err(Error::UnknownUnit {
+                span: 3..4,
+                unit: String::from("d"),
+                expected: expected_units(GO_UNITS),
+            })
+        );
+        assert_eq!(
+            SignedDurationString::parse_go("+.s"),
+            Err(Error::Format {
+                span: 1..2,
+                token: String::from("."),
+                expected: GO_FORMAT,
+            })
+        );
+        assert_eq!(
+            SignedDurationString::parse_go("1h s"),
+            Err(Error::UnknownUnit {
+                span: 1..4,
+                unit: String::from("h s"),
+                expected: expected_units(GO_UNITS),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_go_error_display() {
+        let display = |input| {
+            SignedDurationString::parse_go(input)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            display("1h1d"),
+            "unknown unit `d` at 3..4, expected `ns`, `us`, `\u{b5}s`, `\u{3bc}s`, `ms`, `s`, `m` or `h`"
+        );
+        assert_eq!(
+            display(".h"),
+            "invalid `.` at 0..1, must be a Go duration such as `1h30m` or `-1.5s`"
+        );
+        assert_eq!(display(""), "missing time duration");
+    }
+
+    #[test]
+    fn test_parse_go_overflow() {
+        for input in [
+            "9223372036854775808ns",
+            "9223372036854775.808us",
+            "9223372036854ms775us808ns",
+            "-9223372036854775809ns",
+            "99999999999999999999h",
+            "9223372036854775808ns9223372036854775808ns",
+        ] {
+            assert_eq!(SignedDurationString::parse_go(input), Err(Error::Overflow));
+        }
+    }
+
+    #[test]
+    fn test_parse_go_unsigned() {
+        assert_eq!(
+            DurationString::parse_go("1.5h"),
+            Ok(DurationString::new(Duration::from_secs(5400)))
+        );
+        assert_eq!(DurationString::parse_go("-1.5h"), Err(Error::Negative));
+        assert_eq!(
+            DurationString::parse_go("-0s"),
+            Ok(DurationString::default())
+        );
+    }
+
+    // Cases from Go's `time.TestDurationString`
+    #[test]
+    fn test_to_go_string() {
+        let cases = [
+            ("0s", Duration::ZERO),
+            ("1ns", Duration::from_nanos(1)),
+            ("1.1\u{b5}s", Duration::from_nanos(1100)),
+            ("2.2ms", Duration::from_micros(2200)),
+            ("3.3s", Duration::from_millis(3300)),
+            ("4m5s", Duration::from_secs(245)),
+            ("4m5.001s", Duration::from_millis(245_001)),
+            ("5h6m7.001s", Duration::from_millis(18_367_001)),
+            ("8m0.000000001s", Duration::new(480, 1)),
+            ("2h0m0s", Duration::from_secs(7200)),
+            ("2562047h47m16.854775807s", MAX),
+        ];
+        for (expected, duration) in cases {
+            assert_eq!(DurationString::new(duration).to_go_string(), expected);
+            assert_eq!(go(expected), positive(duration));
+        }
+    }
+
+    #[test]
+    fn test_to_go_string_signed() {
+        assert_eq!(negative(MIN).to_go_string(), "-2562047h47m16.854775808s");
+        assert_eq!(
+            negative(Duration::from_millis(1500)).to_go_string(),
+            "-1.5s"
+        );
+        assert_eq!(
+            negative(Duration::from_micros(1)).to_go_string(),
+            "-1\u{b5}s"
+        );
+        assert_eq!(go("-1.5h").to_go_string(), "-1h30m0s");
+    }
+}
@@ -0,0 +1,391 @@
+//! Human readable output such as `1 hour, 30 minutes` or `in about 2 days`.
+
+use crate::{
+    DurationFormatter, DurationString, RoundingMode, SignedDurationString, Unit, UnitSystem,
+};
+
+/// The words [`Humanizer`] writes in, see [`Locale::ENGLISH`] and [`Locale::DANISH`].
+///
+/// Each unit has a singular and a plural name, the singular is used for a
+/// count of exactly one. Other languages are supported by filling in the fields.
+///
+/// ```rust
+/// use duration_string::{DurationString, Humanizer, Locale};
+/// use std::time::Duration;
+///
+/// let german = Locale {
+///     hour: ["Stunde", "Stunden"],
+///     minute: ["Minute", "Minuten"],
+///     and: "und",
+///     ..Locale::ENGLISH
+/// };
+/// let humanizer = Humanizer::new().locale(german);
+/// let d = DurationString::new(Duration::from_secs(5400));
+/// assert_eq!(humanizer.humanize(d), "1 Stunde, 30 Minuten");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Locale {
+    pub nanosecond: [&'static str; 2],
+    pub microsecond: [&'static str; 2],
+    pub millisecond: [&'static str; 2],
+    pub second: [&'static str; 2],
+    pub minute: [&'static str; 2],
+    pub hour: [&'static str; 2],
+    pub day: [&'static str; 2],
+    pub week: [&'static str; 2],
+    pub month: [&'static str; 2],
+    pub year: [&'static str; 2],
+    /// Joins the last two units with [`Conjunction::And`].
+    pub and: &'static str,
+    /// Marks an approximate duration, see [`Humanizer::approximate`].
+    pub about: &'static str,
+    /// Text before and after a duration in the future, such as `in 2 days`.
+    pub future: [&'static str; 2],
+    /// Text before and after a duration in the past, such as `2 days ago`.
+    pub past: [&'static str; 2],
+    /// A relative duration of zero.
+    pub now: &'static str,
+}
+
+impl Locale {
+    pub const ENGLISH: Self = Self {
+        nanosecond: ["nanosecond", "nanoseconds"],
+        microsecond: ["microsecond", "microseconds"],
+        millisecond: ["millisecond", "milliseconds"],
+        second: ["second", "seconds"],
+        minute: ["minute", "minutes"],
+        hour: ["hour", "hours"],
+        day: ["day", "days"],
+        week: ["week", "weeks"],
+        month: ["month", "months"],
+        year: ["year", "years"],
+        and: "and",
+        about: "about",
+        future: ["in ", ""],
+        past: ["", " ago"],
+        now: "now",
+    };
+
+    pub const DANISH: Self = Self {
+        nanosecond: ["nanosekund", "nanosekunder"],
+        microsecond: ["mikrosekund", "mikrosekunder"],
+        millisecond: ["millisekund", "millisekunder"],
+        second: ["sekund", "sekunder"],
+        minute: ["minut", "minutter"],
+        hour: ["time", "timer"],
+        day: ["dag", "dage"],
+        week: ["uge", "uger"],
+        month: ["m\u{e5}ned", "m\u{e5}neder"],
+        year: ["\u{e5}r", "\u{e5}r"],
+        and: "og",
+        about: "cirka",
+        future: ["om ", ""],
+        past: ["for ", " siden"],
+        now: "nu",
+    };
+
+    fn unit(&self, unit: Unit, count: u128) -> &'static str {
+        let names = match unit {
+            Unit::Nanosecond => self.nanosecond,
+            Unit::Microsecond => self.microsecond,
+            Unit::Millisecond => self.millisecond,
+            Unit::Second => self.second,
+            Unit::Minute => self.minute,
+            Unit::Hour => self.hour,
+            Unit::Day => self.day,
+            Unit::Week => self.week,
+            Unit::Month => self.month,
+            Unit::Year => self.year,
+        };
+        names[usize::from(count != 1)]
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// How the units of a humanized duration are joined.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum Conjunction {
+    /// `1 day, 2 hours, 3 minutes`
+    #[default]
+    Commas,
+    /// `1 day, 2 hours and 3 minutes`, using [`Locale::and`].
+    And,
+}
+
+/// Renders a [`DurationString`] in words, such as `1 hour, 30 minutes` or
+/// `in about 2 days`.
+///
+/// ```rust
+/// use duration_string::{Conjunction, DurationString, Humanizer, Locale, SignedDurationString};
+/// use std::time::Duration;
+///
+/// let d = DurationString::new(Duration::from_secs(5400));
+/// assert_eq!(d.humanize(), "1 hour, 30 minutes");
+///
+/// let humanizer = Humanizer::new().conjunction(Conjunction::And);
+/// assert_eq!(humanizer.humanize(d), "1 hour and 30 minutes");
+///
+/// let humanizer = Humanizer::new().approximate(true);
+/// let d: SignedDurationString = "-1d22h".parse().unwrap();
+/// assert_eq!(humanizer.relative(d), "about 2 days ago");
+/// assert_eq!(humanizer.locale(Locale::DANISH).relative(d), "for cirka 2 dage siden");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Humanizer {
+    locale: Locale,
+    conjunction: Conjunction,
+    approximate: bool,
+    max_units: Option<usize>,
+    smallest_unit: Option<Unit>,
+    unit_system: UnitSystem,
+}
+
+impl Humanizer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The language to write in, [`Locale::ENGLISH`] by default.
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    #[must_use]
+    pub fn conjunction(mut self, conjunction: Conjunction) -> Self {
+        self.conjunction = conjunction;
+        self
+    }
+
+    /// Round to the nearest value of the largest unit, or of
+    /// [`Humanizer::max_units`] units, and prefix [`Locale::about`] when that
+    /// changed the duration, e.g. `about 2 hours`.
+    #[must_use]
+    pub fn approximate(mut self, approximate: bool) -> Self {
+        self.approximate = approximate;
+        self
+    }
+
+    /// The maximum number of units, see [`DurationFormatter::max_components`].
+    /// The remainder is dropped unless the output is approximate.
+    #[must_use]
+    pub fn max_units(mut self, max_units: usize) -> Self {
+        self.max_units = Some(max_units);
+        self
+    }
+
+    /// The smallest unit written, see [`DurationFormatter::smallest_unit`].
+    #[must_use]
+    pub fn smallest_unit(mut self, unit: Unit) -> Self {
+        self.smallest_unit = Some(unit);
+        self
+    }
+
+    /// The length of a year and whether months are written, see [`UnitSystem`].
+    #[must_use]
+    pub fn unit_system(mut self, unit_system: UnitSystem) -> Self {
+        self.unit_system = unit_system;
+        self
+    }
+
+    fn formatter(&self) -> DurationFormatter {
+        let mut formatter = DurationFormatter::new().unit_system(self.unit_system);
+        if let Some(unit) = self.smallest_unit {
+            formatter = formatter.smallest_unit(unit);
+        }
+        if self.approximate {
+            formatter = formatter
+                .rounding(RoundingMode::Nearest)
+                .max_components(self.max_units.unwrap_or(1));
+        } else if let Some(max_units) = self.max_units {
+            formatter = formatter.max_components(max_units);
+        }
+        formatter
+    }
+
+    /// Renders `duration` in words, e.g. `1 hour, 30 minutes`.
+    #[must_use]
+    pub fn humanize(&self, duration: DurationString) -> String {
+        let formatter = self.formatter();
+        let components: Vec<(u128, Unit)> = formatter.components(duration).collect();
+        if components.is_empty() {
+            return format!("0 {}", self.locale.unit(formatter.zero_unit(), 0));
+        }
+
+        let words: Vec<String> = components
+            .iter()
+            .map(|(count, unit)| format!("{count} {}", self.locale.unit(*unit, *count)))
+            .collect();
+        let mut text = match (self.conjunction, words.split_last()) {
+            (Conjunction::And, Some((last, rest))) if !rest.is_empty() => {
+                format!("{} {} {last}", rest.join(", "), self.locale.and)
+            }
+            _ => words.join(", "),
+        };
+
+        let rounded: u128 = components
+            .iter()
+            .map(|(count, unit)| count * self.unit_system.unit_in_nano(*unit))
+            .sum();
+        if self.approximate && rounded != duration.as_nanos() {
+            text = format!("{} {text}", self.locale.about);
+        }
+        text
+    }
+
+    /// Renders `duration` relative to now, e.g. `in 2 days` or `2 days ago`.
+    #[must_use]
+    pub fn relative(&self, duration: SignedDurationString) -> String {
+        let magnitude = duration.abs();
+        if self.formatter().components(magnitude).next().is_none() {
+            return self.locale.now.to_string();
+        }
+        let [before, after] = if duration.is_negative() {
+            self.locale.past
+        } else {
+            self.locale.future
+        };
+        format!("{before}{}{after}", self.humanize(magnitude))
+    }
+}
+
+impl DurationString {
+    /// Renders the duration in English words, e.g. `1 hour, 30 minutes`, see
+    /// [`Humanizer`] for other options.
+    #[must_use]
+    pub fn humanize(&self) -> String {
+        Humanizer::new().humanize(*self)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn humanize(humanizer: &Humanizer, duration: Duration) -> String {
+        humanizer.humanize(DurationString::new(duration))
+    }
+
+    fn relative(humanizer: &Humanizer, input: &str) -> String {
+        humanizer.relative(input.parse().unwrap())
+    }
+
+    #[test]
+    fn test_humanize() {
+        let humanizer = Humanizer::new();
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(5400)),
+            "1 hour, 30 minutes"
+        );
+        assert_eq!(humanize(&humanizer, Duration::from_secs(1)), "1 second");
+        assert_eq!(humanize(&humanizer, Duration::ZERO), "0 seconds");
+        assert_eq!(
+            humanize(&humanizer, Duration::new(90_061, 2_000_000)),
+            "1 day, 1 hour, 1 minute, 1 second, 2 milliseconds"
+        );
+    }
+
+    #[test]
+    fn test_humanize_conjunction() {
+        let humanizer = Humanizer::new().conjunction(Conjunction::And);
+        assert_eq!(humanize(&humanizer, Duration::from_secs(60)), "1 minute");
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(5400)),
+            "1 hour and 30 minutes"
+        );
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(90_060)),
+            "1 day, 1 hour and 1 minute"
+        );
+    }
+
+    #[test]
+    fn test_humanize_approximate() {
+        let humanizer = Humanizer::new().approximate(true);
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(6000)),
+            "about 2 hours"
+        );
+        assert_eq!(humanize(&humanizer, Duration::from_secs(7200)), "2 hours");
+        assert_eq!(humanize(&humanizer, Duration::from_secs(59)), "59 seconds");
+        let humanizer = humanizer.max_units(2);
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(5430)),
+            "about 1 hour, 31 minutes"
+        );
+    }
+
+    #[test]
+    fn test_humanize_max_units() {
+        let humanizer = Humanizer::new().max_units(1);
+        assert_eq!(humanize(&humanizer, Duration::from_secs(6000)), "1 hour");
+        let humanizer = Humanizer::new().smallest_unit(Unit::Second);
+        assert_eq!(
+            humanize(&humanizer, Duration::from_millis(1500)),
+            "1 second"
+        );
+        assert_eq!(
+            humanize(&humanizer, Duration::from_millis(500)),
+            "0 seconds"
+        );
+    }
+
+    #[test]
+    fn test_humanize_danish() {
+        let humanizer = Humanizer::new()
+            .locale(Locale::DANISH)
+            .conjunction(Conjunction::And);
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(5400)),
+            "1 time og 30 minutter"
+        );
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(31_556_926 * 2 + 86_400)),
+            "2 \u{e5}r og 1 dag"
+        );
+        assert_eq!(relative(&humanizer, "2d"), "om 2 dage");
+        assert_eq!(relative(&humanizer, "-1w"), "for 1 uge siden");
+        assert_eq!(relative(&humanizer, "0s"), "nu");
+    }
+
+    #[test]
+    fn test_humanize_unit_system() {
+        let system = UnitSystem::new()
+            .year(crate::YearLength::Julian)
+            .months(true);
+        let humanizer = Humanizer::new().unit_system(system).approximate(true);
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(2_629_800 * 3)),
+            "3 months"
+        );
+        assert_eq!(
+            humanize(&humanizer, Duration::from_secs(31_557_600 + 1000)),
+            "about 1 year"
+        );
+    }
+
+    #[test]
+    fn test_relative() {
+        let humanizer = Humanizer::new();
+        assert_eq!(relative(&humanizer, "2d"), "in 2 days");
+        assert_eq!(relative(&humanizer, "-1h30m"), "1 hour, 30 minutes ago");
+        assert_eq!(relative(&humanizer, "-0s"), "now");
+        let humanizer = humanizer.approximate(true);
+        assert_eq!(relative(&humanizer, "1d23h"), "in about 2 days");
+    }
+
+    #[test]
+    fn test_duration_string_humanize() {
+        let d = DurationString::new(Duration::from_secs(172_800));
+        assert_eq!(d.humanize(), "2 days");
+    }
+}
@@ -0,0 +1,439 @@
+//! ISO 8601 durations such as `PT1H30M5.5S`, `P3DT4H` or `P1W`.
+
+use crate::parser::{duration_from_nanos, quantity_in_nano};
+use crate::unit::{
+    DAY_IN_NANO, HOUR_IN_NANO, MINUTE_IN_NANO, SECOND_IN_NANO, WEEK_IN_NANO, YEAR_IN_NANO,
+};
+use crate::{DurationString, Error, Result};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// How the ISO 8601 year and month components, whose length depends on the
+/// calendar, are converted into a fixed duration.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum CalendarApproximation {
+    /// Years and months are rejected with [`Error::CalendarUnit`].
+    #[default]
+    Reject,
+    /// A year is the `y` unit of this crate, 365.2422 days, and a month is a
+    /// twelfth of it truncated to whole nanoseconds.
+    Average,
+    /// A year is 365 days and a month is 30 days.
+    Fixed,
+}
+
+impl CalendarApproximation {
+    /// The length of a year and a month in nanoseconds, if they are accepted.
+    fn year_and_month(self) -> Option<(u128, u128)> {
+        match self {
+            Self::Reject => None,
+            Self::Average => Some((YEAR_IN_NANO, YEAR_IN_NANO / 12)),
+            Self::Fixed => Some((365 * DAY_IN_NANO, 30 * DAY_IN_NANO)),
+        }
+    }
+}
+
+/// The designators in the order they must appear, those after `T` are in the time part.
+const DESIGNATORS: [(char, bool); 7] = [
+    ('Y', false),
+    ('M', false),
+    ('W', false),
+    ('D', false),
+    ('H', true),
+    ('M', true),
+    ('S', true),
+];
+
+fn designator_in_nano(
+    slot: usize,
+    approximation: CalendarApproximation,
+    span: Range<usize>,
+) -> Result<u128> {
+    let calendar = |pick: fn((u128, u128)) -> u128, unit: &str| {
+        approximation
+            .year_and_month()
+            .map(pick)
+            .ok_or_else(|| Error::CalendarUnit {
+                span: span.clone(),
+                unit: unit.to_string(),
+            })
+    };
+    match slot {
+        0 => calendar(|(year, _)| year, "Y"),
+        1 => calendar(|(_, month)| month, "M"),
+        2 => Ok(WEEK_IN_NANO),
+        3 => Ok(DAY_IN_NANO),
+        4 => Ok(HOUR_IN_NANO),
+        5 => Ok(MINUTE_IN_NANO),
+        _ => Ok(SECOND_IN_NANO),
+    }
+}
+
+/// The format of ISO 8601 durations, as the `expected` of [`Error::Format`].
+const ISO8601_FORMAT: &str = "an ISO 8601 duration such as `P3DT4H` or `PT1H30M5.5S`";
+
+/// The designators allowed in the date or the time part, for unit errors.
+fn expected_designators(time: bool) -> Vec<String> {
+    let mut expected: Vec<String> = DESIGNATORS
+        .iter()
+        .filter(|(_, in_time)| *in_time == time)
+        .map(|(designator, _)| designator.to_string())
+        .collect();
+    if !time {
+        expected.push(String::from("T"));
+    }
+    expected
+}
+
+fn parse_iso8601(input: &str, approximation: CalendarApproximation) -> Result<DurationString> {
+    let format = |span: Range<usize>| Error::Format {
+        token: input[span.clone()].to_string(),
+        span,
+        expected: ISO8601_FORMAT,
+    };
+    match input.chars().next() {
+        None => return Err(Error::Empty),
+        Some('P') => {}
+        Some(c) => return Err(format(0..c.len_utf8())),
+    }
+
+    let bytes = input.as_bytes();
+    let mut i = 1;
+    let mut time: Option<usize> = None;
+    let mut time_components = 0;
+    let mut components = 0;
+    let mut last: Option<usize> = None;
+    let mut fraction: Option<Range<usize>> = None;
+    let mut total: u128 = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'T' {
+            if time.is_some() {
+                return Err(Error::DuplicateUnit {
+                    span: i..i + 1,
+                    unit: String::from("T"),
+                });
+            }
+            time = Some(i);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < bytes.len() && matches!(bytes[i], b'0'..=b'9' | b'.' | b',') {
+            i += 1;
+        }
+        let designator = input[i..]
+            .chars()
+            .next()
+            .ok_or_else(|| Error::MissingUnit {
+                span: i..i,
+                expected: expected_designators(time.is_some()),
+            })?;
+        let span = i..i + designator.len_utf8();
+        if start == i {
+            return Err(Error::MissingQuantity { span });
+        }
+        let slot = DESIGNATORS
+            .iter()
+            .position(|d| *d == (designator, time.is_some()))
+            .ok_or_else(|| Error::UnknownUnit {
+                span: span.clone(),
+                unit: designator.to_string(),
+                expected: expected_designators(time.is_some()),
+            })?;
+        if last == Some(slot) {
+            return Err(Error::DuplicateUnit {
+                span,
+                unit: designator.to_string(),
+            });
+        }
+        if last.is_some_and(|last| slot < last) {
+            return Err(format(span));
+        }
+        // Only the last component may have a fraction
+        if let Some(span) = fraction {
+            return Err(format(span));
+        }
+
+        let quantity = input[start..i].replace(',', ".");
+        if quantity.contains('.') {
+            fraction = Some(start..i);
+        }
+        let unit_in_nano = designator_in_nano(slot, approximation, span.clone())?;
+        let nanos = quantity_in_nano(&quantity, start..i, unit_in_nano, ISO8601_FORMAT)?;
+        total = total.checked_add(nanos).ok_or(Error::Overflow)?;
+
+        last = Some(slot);
+        components += 1;
+        if time.is_some() {
+            time_components += 1;
+        }
+        i = span.end;
+    }
+
+    if components == 0 {
+        return Err(format(0..input.len()));
+    }
+    if let (Some(t), 0) = (time, time_components) {
+        return Err(format(t..t + 1));
+    }
+    duration_from_nanos(total).map(DurationString::new)
+}
+
+impl DurationString {
+    /// Parses an ISO 8601 duration such as `PT1H30M5.5S`, `P3DT4H` or `P1W`.
+    ///
+    /// The last component may have a fraction, written with `.` or `,`.
+    /// Years and months have no fixed length and are rejected, see
+    /// [`DurationString::parse_iso8601_with`] to accept them.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let d = DurationString::parse_iso8601("PT1H30M5.5S").unwrap();
+    /// assert_eq!(d, Duration::from_millis(5_405_500));
+    /// assert_eq!(d.to_iso8601_string(), "PT1H30M5.5S");
+    /// assert!(DurationString::parse_iso8601("P1M").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `input` is not an ISO 8601 duration, and
+    /// [`Error::CalendarUnit`] for years and months.
+    pub fn parse_iso8601(input: &str) -> Result<Self> {
+        parse_iso8601(input, CalendarApproximation::Reject)
+    }
+
+    /// Parses an ISO 8601 duration, converting years and months with `approximation`.
+    ///
+    /// ```rust
+    /// use duration_string::{CalendarApproximation, DurationString};
+    /// use std::time::Duration;
+    ///
+    /// let d = DurationString::parse_iso8601_with("P1Y2M", CalendarApproximation::Fixed);
+    /// assert_eq!(d.unwrap(), Duration::from_secs(425 * 86_400));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `input` is not an ISO 8601 duration, see
+    /// [`DurationString::parse_iso8601`].
+    pub fn parse_iso8601_with(input: &str, approximation: CalendarApproximation) -> Result<Self> {
+        parse_iso8601(input, approximation)
+    }
+
+    /// Formats as an ISO 8601 duration such as `PT1H30M5.5S` or `P3DT4H`.
+    ///
+    /// Days are the largest component so the result never depends on the
+    /// calendar, and zero is `PT0S`.
+    #[must_use]
+    pub fn to_iso8601_string(&self) -> String {
+        let ns = self.as_nanos();
+        if ns == 0 {
+            return String::from("PT0S");
+        }
+        let mut s = String::from("P");
+        // Writing to a `String` never fails
+        let days = ns / DAY_IN_NANO;
+        if days > 0 {
+            let _ = write!(s, "{days}D");
+        }
+        let time = ns % DAY_IN_NANO;
+        if time == 0 {
+            return s;
+        }
+        s.push('T');
+        let hours = time / HOUR_IN_NANO;
+        if hours > 0 {
+            let _ = write!(s, "{hours}H");
+        }
+        let minutes = time % HOUR_IN_NANO / MINUTE_IN_NANO;
+        if minutes > 0 {
+            let _ = write!(s, "{minutes}M");
+        }
+        let seconds = time % MINUTE_IN_NANO;
+        if seconds > 0 {
+            let _ = write!(s, "{}", seconds / SECOND_IN_NANO);
+            let nanos = seconds % SECOND_IN_NANO;
+            if nanos > 0 {
+                let digits = format!("{nanos:09}");
+                let _ = write!(s, ".{}", digits.trim_end_matches('0'));
+            }
+            s.push('S');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn iso(input: &str) -> Result<Duration> {
+        DurationString::parse_iso8601(input).map(Duration::from)
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(iso("PT1H30M5.5S"), Ok(Duration::from_millis(5_405_500)));
+        assert_eq!(iso("PT1H30M5,5S"), Ok(Duration::from_millis(5_405_500)));
+        assert_eq!(iso("P3DT4H"), Ok(Duration::from_secs(273_600)));
+        assert_eq!(iso("P1W"), Ok(Duration::from_secs(604_800)));
+        assert_eq!(iso("P2D"), Ok(Duration::from_secs(172_800)));
+        assert_eq!(iso("PT0S"), Ok(Duration::ZERO));
+        assert_eq!(iso("PT36H"), Ok(Duration::from_secs(129_600)));
+        assert_eq!(iso("PT0.000000001S"), Ok(Duration::from_nanos(1)));
+        assert_eq!(iso("P0.5D"), Ok(Duration::from_secs(43_200)));
+        assert_eq!(iso("PT1.5M"), Ok(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_parse_iso8601_calendar_units() {
+        assert_eq!(
+            iso("P1M"),
+            Err(Error::CalendarUnit {
+                span: 2..3,
+                unit: String::from("M"),
+            })
+        );
+        assert_eq!(
+            iso("P1Y2D"),
+            Err(Error::CalendarUnit {
+                span: 2..3,
+                unit: String::from("Y"),
+            })
+        );
+        // Minutes are not calendar dependent
+        assert_eq!(iso("PT1M"), Ok(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_parse_iso8601_approximation() {
+        let parse = |input, approximation| {
+            DurationString::parse_iso8601_with(input, approximation).map(Duration::from)
+        };
+        assert_eq!(
+            parse("P1Y", CalendarApproximation::Average),
+            Ok(Duration::from_secs(31_556_926))
+        );
+        assert_eq!(
+            parse("P1M", CalendarApproximation::Average),
+            Ok(Duration::new(2_629_743, 833_333_333))
+        );
+        assert_eq!(
+            parse("P1Y1M1DT1H", CalendarApproximation::Fixed),
+            Ok(Duration::from_secs(396 * 86_400 + 3600))
+        );
+        assert!(parse("P1Y", CalendarApproximation::Reject).is_err());
+    }
+
+    #[test]
+    fn test_parse_iso8601_error_display() {
+        let display = |input| iso(input).unwrap_err().to_string();
+        assert_eq!(
+            display("P1H"),
+            "unknown unit `H` at 2..3, expected `Y`, `M`, `W`, `D` or `T`"
+        );
+        assert_eq!(
+            display("PT5"),
+            "missing unit at 3, expected `H`, `M` or `S`"
+        );
+        assert_eq!(
+            display("P"),
+            "invalid `P` at 0..1, must be an ISO 8601 duration such as `P3DT4H` or `PT1H30M5.5S`"
+        );
+    }
+
+    #[test]
+    fn test_parse_iso8601_errors() {
+        assert_eq!(iso(""), Err(Error::Empty));
+        assert_eq!(
+            iso("T1H"),
+            Err(Error::Format {
+                span: 0..1,
+                token: String::from("T"),
+                expected: ISO8601_FORMAT,
+            })
+        );
+        assert_eq!(
+            iso("P"),
+            Err(Error::Format {
+                span: 0..1,
+                token: String::from("P"),
+                expected: ISO8601_FORMAT,
+            })
+        );
+        assert_eq!(
+            iso("P1DT"),
+            Err(Error::Format {
+                span: 3..4,
+                token: String::from("T"),
+                expected: ISO8601_FORMAT,
+            })
+        );
+        assert_eq!(
+            iso("PT5"),
+            Err(Error::MissingUnit {
+                span: 3..3,
+                expected: expected_designators(true),
+            })
+        );
+        assert_eq!(iso("PTS"), Err(Error::MissingQuantity { span: 2..3 }));
+        assert_eq!(
+            iso("P1H"),
+            Err(Error::UnknownUnit {
+                span: 2..3,
+                unit: String::from("H"),
+                expected: expected_designators(false),
+            })
+        );
+        assert_eq!(
+            iso("PT1S1S"),
+            Err(Error::DuplicateUnit {
+                span: 5..6,
+                unit: String::from("S"),
+            })
+        );
+        assert_eq!(
+            iso("PT1S1H"),
+            Err(Error::Format {
+                span: 5..6,
+                token: String::from("H"),
+                expected: ISO8601_FORMAT,
+            })
+        );
+        assert_eq!(
+            iso("PT1.5H1M"),
+            Err(Error::Format {
+                span: 2..5,
+                token: String::from("1.5"),
+                expected: ISO8601_FORMAT,
+            })
+        );
+        assert_eq!(
+            iso("PT0.0000000001S"),
+            Err(Error::Precision { span: 2..14 })
+        );
+    }
+
+    #[test]
+    fn test_to_iso8601_string() {
+        let iso_string = |duration| DurationString::new(duration).to_iso8601_string();
+        assert_eq!(iso_string(Duration::ZERO), "PT0S");
+        assert_eq!(iso_string(Duration::from_millis(5_405_500)), "PT1H30M5.5S");
+        assert_eq!(iso_string(Duration::from_secs(273_600)), "P3DT4H");
+        assert_eq!(iso_string(Duration::from_secs(604_800)), "P7D");
+        assert_eq!(iso_string(Duration::from_nanos(1)), "PT0.000000001S");
+        assert_eq!(iso_string(Duration::from_secs(86_401)), "P1DT1S");
+    }
+
+    #[test]
+    fn test_iso8601_round_trip() {
+        let duration = Duration::new(31_556_927, 1_001_001);
+        let s = DurationString::new(duration).to_iso8601_string();
+        assert_eq!(iso(&s), Ok(duration));
+    }
+}
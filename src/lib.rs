@@ -5,9 +5,17 @@
 //!
 //! Takes a `String` such as `100ms`, `2s`, `5m 30s`, `1h10m` and converts it into a `Duration`.
 //!
-//! Takes a `Duration` and converts it into `String`.
+//! Takes a `Duration` and converts it into a `String` such as `1h30m15s`, which
+//! always parses back into the same `Duration`.
 //!
-//! The `String` format is a multiply of `[0-9]+(ns|us|ms|[smhdwy])`
+//! The `String` format is a multiply of `[0-9]+(.[0-9]+)?(ns|us|ms|[smhdwy])`.
+//! Fractional quantities such as `1.5h` or `0.25s` are parsed exactly, down to
+//! nanosecond precision.
+//! Microseconds may also be written with the micro sign, as in `5µs`.
+//! Clock notation such as `01:30:00`, `90:00` or `2d 03:00:00.250` is accepted too.
+//! A year is 365.2422 days by default, `UnitSystem` configures 365 or 365.25 days
+//! and a `mo` month unit for both parsing and formatting.
+//! Extra units such as `3 epochs 2 slots` can be registered with `CustomUnits`.
 //!
 //! ## Example
 //!
@@ -37,6 +45,17 @@
 //! assert_eq!(d, String::from("100ms"));
 //! ```
 //!
+//! Constants can be written in the same notation, an invalid literal fails the build:
+//!
+//! ```rust
+//! use duration_string::{duration, DurationString};
+//!
+//! const TIMEOUT: DurationString = duration!("5m30s");
+//! ```
+//!
+//! With the `macros` feature, `dur!("5m30s")` does the same as a procedural
+//! macro whose errors point at the literal, such as `unknown unit "mz"`.
+//!
 //! ## Serde support
 //!
 //! You can enable _serialization/deserialization_ support by adding the feature `serde`
@@ -44,7 +63,7 @@
 //! - Add `serde` feature
 //!
 //!    ```toml
-//!    duration-string = { version = "0.5.2", features = ["serde"] }
+//!    duration-string = { version = "0.6.0", features = ["serde"] }
 //!    ```
 //!
 //! - Add derive to struct
@@ -61,7 +80,6 @@
 //!
 #![cfg_attr(feature = "serde", doc = "```rust")]
 #![cfg_attr(not(feature = "serde"), doc = "```ignore")]
-//! ```
 //! use duration_string::DurationString;
 //! use serde::{Deserialize, Serialize};
 //! use serde_json;
@@ -86,43 +104,286 @@
 #[cfg(feature = "serde")]
 use std::marker::PhantomData;
 use std::num::ParseIntError;
+use std::ops::Range;
 use std::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 use std::str::FromStr;
 use std::time::Duration;
-
-const YEAR_IN_NANO: u128 = 31_556_926_000_000_000;
-const WEEK_IN_NANO: u128 = 604_800_000_000_000;
-const DAY_IN_NANO: u128 = 86_400_000_000_000;
-const HOUR_IN_NANO: u128 = 3_600_000_000_000;
-const MINUTE_IN_NANO: u128 = 60_000_000_000;
-const SECOND_IN_NANO: u128 = 1_000_000_000;
-const MILLISECOND_IN_NANO: u128 = 1_000_000;
-const MICROSECOND_IN_NANO: u128 = 1000;
-
-const HOUR_IN_SECONDS: u32 = 3600;
-const MINUTE_IN_SECONDS: u32 = 60;
-const DAY_IN_SECONDS: u32 = 86_400;
-const WEEK_IN_SECONDS: u32 = 604_800;
-const YEAR_IN_SECONDS: u32 = 31_556_926;
+use unit::UNITS;
+
+mod clock;
+mod custom_units;
+mod digit;
+mod format;
+mod go;
+mod humanize;
+mod iso8601;
+mod literal;
+mod parser;
+mod scan;
+mod signed;
+mod systemd;
+mod unit;
+mod unit_system;
+
+pub use custom_units::CustomUnits;
+pub use format::{DurationFormatter, RoundingMode};
+pub use humanize::{Conjunction, Humanizer, Locale};
+pub use iso8601::CalendarApproximation;
+pub use parser::DurationParser;
+pub use scan::Scan;
+pub use signed::SignedDurationString;
+pub use systemd::SystemdTimespan;
+pub use unit::Unit;
+pub use unit_system::{UnitSystem, YearLength};
+
+#[cfg(feature = "macros")]
+pub use duration_string_macros::dur;
+// Lets the `::duration_string` paths of `dur!` resolve within this crate
+#[cfg(all(test, feature = "macros"))]
+extern crate self as duration_string;
+
+/// A [`DurationString`] constant parsed at compile time, so an invalid
+/// literal fails the build instead of panicking at runtime.
+///
+/// ```rust
+/// use duration_string::{duration, DurationString};
+/// use std::time::Duration;
+///
+/// const TIMEOUT: DurationString = duration!("5m30s");
+/// assert_eq!(TIMEOUT, Duration::from_secs(330));
+/// assert_eq!(duration!("1.5h"), Duration::from_secs(5400));
+/// ```
+///
+/// ```compile_fail
+/// let timeout = duration_string::duration!("5x");
+/// ```
+///
+/// See [`DurationString::from_str_const`] for the accepted format.
+#[macro_export]
+macro_rules! duration {
+    ($literal:expr) => {{
+        const DURATION: $crate::DurationString = $crate::DurationString::from_str_const($literal);
+        DURATION
+    }};
+}
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The format of [`DurationParser`], as the `expected` of [`Error::Format`].
+pub(crate) const FORMAT: &str = "multiples of `[0-9]+(.[0-9]+)?(ns|us|ms|[smhdwy])`";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
-    Format,
+    /// `token` at the byte range `span` of the input does not fit the format
+    /// described by `expected`.
+    Format {
+        span: Range<usize>,
+        token: String,
+        expected: &'static str,
+    },
+    /// The input is empty or only whitespace.
+    Empty,
+    /// `unit` at the byte range `span` of the input is not one of the
+    /// `expected` units.
+    UnknownUnit {
+        span: Range<usize>,
+        unit: String,
+        expected: Vec<String>,
+    },
+    /// The quantity ending at `span` is not followed by one of the `expected`
+    /// units, e.g. `1234`.
+    MissingUnit {
+        span: Range<usize>,
+        expected: Vec<String>,
+    },
+    /// The unit starting at `span` is not preceded by a quantity, e.g. `ms`.
+    MissingQuantity {
+        span: Range<usize>,
+    },
+    /// Whitespace at the byte range `span` of the input is not between two
+    /// components, only reported by a strict [`DurationParser`].
+    UnexpectedWhitespace {
+        span: Range<usize>,
+    },
+    /// `unit` at the byte range `span` of the input was already used, e.g. `1h 2h`.
+    DuplicateUnit {
+        span: Range<usize>,
+        unit: String,
+    },
     Overflow,
-    ParseInt(ParseIntError),
+    /// A negative duration was converted into an unsigned one.
+    Negative,
+    /// The quantity at the byte range `span` of the input is not a valid number.
+    ParseInt {
+        span: Range<usize>,
+        source: ParseIntError,
+    },
+    /// `digit` at the byte range `span` of the input is numeric but not an
+    /// accepted digit, see [`DurationParser::unicode_digits`].
+    NonAsciiDigit {
+        span: Range<usize>,
+        digit: char,
+    },
+    /// The fractional quantity at the byte range `span` of the input cannot be
+    /// represented in whole nanoseconds.
+    Precision {
+        span: Range<usize>,
+    },
+    /// `unit` at the byte range `span` of the input has no fixed length, such
+    /// as ISO 8601 months, see [`CalendarApproximation`].
+    CalendarUnit {
+        span: Range<usize>,
+        unit: String,
+    },
+    /// The bytes at `span` are not valid UTF-8, see [`DurationString::from_bytes`].
+    InvalidUtf8 {
+        span: Range<usize>,
+    },
+}
+
+impl Error {
+    /// The byte range of the input where parsing failed, if any.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::Format { span, .. }
+            | Self::UnknownUnit { span, .. }
+            | Self::MissingUnit { span, .. }
+            | Self::MissingQuantity { span }
+            | Self::UnexpectedWhitespace { span }
+            | Self::DuplicateUnit { span, .. }
+            | Self::ParseInt { span, .. }
+            | Self::NonAsciiDigit { span, .. }
+            | Self::Precision { span }
+            | Self::CalendarUnit { span, .. }
+            | Self::InvalidUtf8 { span } => Some(span.clone()),
+            Self::Empty | Self::Overflow | Self::Negative => None,
+        }
+    }
+
+    fn span_mut(&mut self) -> Option<&mut Range<usize>> {
+        match self {
+            Self::Format { span, .. }
+            | Self::UnknownUnit { span, .. }
+            | Self::MissingUnit { span, .. }
+            | Self::MissingQuantity { span }
+            | Self::UnexpectedWhitespace { span }
+            | Self::DuplicateUnit { span, .. }
+            | Self::ParseInt { span, .. }
+            | Self::NonAsciiDigit { span, .. }
+            | Self::Precision { span }
+            | Self::CalendarUnit { span, .. }
+            | Self::InvalidUtf8 { span } => Some(span),
+            Self::Empty | Self::Overflow | Self::Negative => None,
+        }
+    }
+
+    /// Moves the span by `offset` bytes, for errors from parsing a suffix of the input.
+    pub(crate) fn offset(mut self, offset: usize) -> Self {
+        if let Some(span) = self.span_mut() {
+            *span = span.start + offset..span.end + offset;
+        }
+        self
+    }
+
+    /// Renders the error along with the `input` it came from and a caret under
+    /// the offending span.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    ///
+    /// let input = "5m 3x 2s";
+    /// let err = input.parse::<DurationString>().unwrap_err();
+    /// assert_eq!(
+    ///     err.diagnostic(input).to_string(),
+    ///     "unknown unit `x` at 4..5, expected `ns`, `us`, `µs`, `μs`, `ms`, `s`, `m`, `h`, `d`, `w` or `y`\n\
+    ///      5m 3x 2s\n\
+    ///      \x20   ^"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn diagnostic<'a>(&'a self, input: &'a str) -> Diagnostic<'a> {
+        Diagnostic { error: self, input }
+    }
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Format => write!(
+            Self::Format {
+                span,
+                token,
+                expected,
+            } => write!(f, "invalid `{token}` at {span:?}, must be {expected}"),
+            Self::Empty => write!(f, "missing time duration"),
+            Self::UnknownUnit {
+                span,
+                unit,
+                expected,
+            } => {
+                write!(f, "unknown unit `{unit}` at {span:?}")?;
+                write_expected(f, expected)
+            }
+            Self::MissingUnit { span, expected } => {
+                write!(f, "missing unit at {}", span.start)?;
+                write_expected(f, expected)
+            }
+            Self::MissingQuantity { span } => {
+                write!(f, "missing quantity before unit at {}", span.start)
+            }
+            Self::UnexpectedWhitespace { span } => write!(
                 f,
-                "missing time duration format, must be multiples of `[0-9]+(ns|us|ms|[smhdwy])`"
+                "unexpected whitespace at {span:?}, only allowed between components"
             ),
+            Self::DuplicateUnit { span, unit } => {
+                write!(f, "duplicate unit `{unit}` at {span:?}")
+            }
             Self::Overflow => write!(f, "number is too large to fit in target type"),
-            Self::ParseInt(err) => write!(f, "{err}"),
+            Self::Negative => write!(f, "negative duration cannot be made unsigned"),
+            Self::Precision { span } => write!(
+                f,
+                "fractional quantity at {span:?} cannot be represented in whole nanoseconds"
+            ),
+            Self::ParseInt { span, source } => write!(f, "{source} at {span:?}"),
+            Self::NonAsciiDigit { span, digit } => {
+                write!(f, "non-ASCII digit `{digit}` at {span:?}")
+            }
+            Self::CalendarUnit { span, unit } => {
+                write!(f, "calendar unit `{unit}` at {span:?} has no fixed length")
+            }
+            Self::InvalidUtf8 { span } => write!(f, "invalid UTF-8 at {span:?}"),
+        }
+    }
+}
+
+/// Writes `, expected` and the units of an error in backticks, if there are any.
+fn write_expected(f: &mut std::fmt::Formatter<'_>, expected: &[String]) -> std::fmt::Result {
+    let Some((last, rest)) = expected.split_last() else {
+        return Ok(());
+    };
+    write!(f, ", expected ")?;
+    for (i, unit) in rest.iter().enumerate() {
+        let separator = if i == 0 { "" } else { ", " };
+        write!(f, "{separator}`{unit}`")?;
+    }
+    if rest.is_empty() {
+        write!(f, "`{last}`")
+    } else {
+        write!(f, " or `{last}`")
+    }
+}
+
+/// The `expected` units of an error, from the symbols of a grammar.
+pub(crate) fn expected_units<'a>(symbols: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    symbols.into_iter().map(str::to_string).collect()
+}
+
+/// For a `ParseIntError` from outside the input, the span is empty.
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        Self::ParseInt {
+            span: 0..0,
+            source: value,
         }
     }
 }
@@ -130,15 +391,33 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Format | Self::Overflow => None,
-            Self::ParseInt(err) => Some(err),
+            Self::ParseInt { source, .. } => Some(source),
+            _ => None,
         }
     }
 }
 
-impl From<ParseIntError> for Error {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseInt(value)
+/// An [`Error`] displayed with the input it came from, see [`Error::diagnostic`].
+#[derive(Copy, Clone, Debug)]
+pub struct Diagnostic<'a> {
+    error: &'a Error,
+    input: &'a str,
+}
+
+impl std::fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        let Some(span) = self.error.span() else {
+            return Ok(());
+        };
+        let (Some(before), Some(token)) = (self.input.get(..span.start), self.input.get(span))
+        else {
+            // The span does not belong to this input
+            return Ok(());
+        };
+        let offset = before.chars().count();
+        let width = token.chars().count().max(1);
+        write!(f, "\n{}\n{:offset$}{:^<width$}", self.input, "", "")
     }
 }
 
@@ -155,12 +434,121 @@ pub const fn new(duration: Duration) -> DurationString {
     pub fn from_string(duration: String) -> Result<Self> {
         DurationString::try_from(duration)
     }
+
+    /// Parses UTF-8 `bytes` such as a field of a log record, without copying them.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let d = DurationString::from_bytes(b"1m30s").unwrap();
+    /// assert_eq!(d, Duration::from_secs(90));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidUtf8`] if `bytes` is not UTF-8, or the same
+    /// errors as `FromStr`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let input = std::str::from_utf8(bytes).map_err(|err| {
+            let start = err.valid_up_to();
+            let end = err.error_len().map_or(bytes.len(), |len| start + len);
+            Error::InvalidUtf8 { span: start..end }
+        })?;
+        input.parse()
+    }
+
+    /// Parses `input` in a `const` context, with the same format as `FromStr`
+    /// except for clock notation. Only ASCII whitespace is ignored.
+    ///
+    /// Evaluated in a constant, an invalid `input` fails the build, see the
+    /// [`duration!`] macro.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// const RETRY: DurationString = DurationString::from_str_const("1m 30s");
+    /// assert_eq!(RETRY, Duration::from_secs(90));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` does not fit the format or the total does not fit in
+    /// a `Duration`.
+    #[must_use]
+    pub const fn from_str_const(input: &str) -> DurationString {
+        let nanos = match literal::parse_literal(input.as_bytes()) {
+            Ok(nanos) => nanos,
+            Err(err) => panic!("{}", err.message()),
+        };
+        let secs = nanos / unit::SECOND_IN_NANO;
+        assert!(secs <= u64::MAX as u128, "duration literal is too large");
+        // Both casts are in range, the remainder is below one second
+        #[allow(clippy::cast_possible_truncation)]
+        DurationString(Duration::new(
+            secs as u64,
+            (nanos % unit::SECOND_IN_NANO) as u32,
+        ))
+    }
+
+    /// Formats the duration using only the largest unit that divides it evenly,
+    /// e.g. 5415 seconds becomes `5415s` rather than `1h30m15s`.
+    ///
+    /// This is the output format of versions prior to compound formatting.
+    #[must_use]
+    pub fn to_single_unit_string(&self) -> String {
+        let ns = self.0.as_nanos();
+        for unit in UNITS {
+            if ns.is_multiple_of(unit.as_nanos()) {
+                return (ns / unit.as_nanos()).to_string() + unit.symbol();
+            }
+        }
+        unreachable!("every duration is a multiple of one nanosecond")
+    }
+
+    /// Formats the duration through a configured [`DurationFormatter`].
+    ///
+    /// ```rust
+    /// use duration_string::{DurationFormatter, DurationString, Unit};
+    /// use std::time::Duration;
+    ///
+    /// let formatter = DurationFormatter::new()
+    ///     .separator(" ")
+    ///     .smallest_unit(Unit::Second);
+    /// let d = DurationString::new(Duration::from_millis(5_415_250));
+    /// assert_eq!(d.format_with(&formatter), "1h 30m 15s");
+    /// ```
+    #[must_use]
+    pub fn format_with(&self, formatter: &DurationFormatter) -> String {
+        formatter.format(*self)
+    }
+
+    /// Renders the duration like `Display` into `buf`, without allocating.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let mut buf = [0; 64];
+    /// let d = DurationString::new(Duration::from_secs(5415));
+    /// assert_eq!(d.format_into(&mut buf), Ok("1h30m15s"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `buf` is too small, 64 bytes always suffice.
+    pub fn format_into<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> std::result::Result<&'a str, std::fmt::Error> {
+        DurationFormatter::new().format_into(*self, buf)
+    }
 }
 
 impl std::fmt::Display for DurationString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s: String = (*self).into();
-        write!(f, "{s}")
+        DurationFormatter::new().write(*self, f)
     }
 }
 
@@ -171,33 +559,11 @@ fn from(value: DurationString) -> Self {
 }
 
 impl From<DurationString> for String {
+    /// Decomposes the duration into every unit from years down to nanoseconds,
+    /// e.g. 5415 seconds becomes `1h30m15s`. The result parses back into the
+    /// exact same `DurationString`.
     fn from(value: DurationString) -> Self {
-        let ns = value.0.as_nanos();
-        if ns % YEAR_IN_NANO == 0 {
-            return (ns / YEAR_IN_NANO).to_string() + "y";
-        }
-        if ns % WEEK_IN_NANO == 0 {
-            return (ns / WEEK_IN_NANO).to_string() + "w";
-        }
-        if ns % DAY_IN_NANO == 0 {
-            return (ns / DAY_IN_NANO).to_string() + "d";
-        }
-        if ns % HOUR_IN_NANO == 0 {
-            return (ns / HOUR_IN_NANO).to_string() + "h";
-        }
-        if ns % MINUTE_IN_NANO == 0 {
-            return (ns / MINUTE_IN_NANO).to_string() + "m";
-        }
-        if ns % SECOND_IN_NANO == 0 {
-            return (ns / SECOND_IN_NANO).to_string() + "s";
-        }
-        if ns % MILLISECOND_IN_NANO == 0 {
-            return (ns / MILLISECOND_IN_NANO).to_string() + "ms";
-        }
-        if ns % MICROSECOND_IN_NANO == 0 {
-            return (ns / MICROSECOND_IN_NANO).to_string() + "us";
-        }
-        ns.to_string() + "ns"
+        DurationFormatter::new().format(value)
     }
 }
 
@@ -219,53 +585,7 @@ impl FromStr for DurationString {
     type Err = Error;
 
     fn from_str(duration: &str) -> std::result::Result<Self, Self::Err> {
-        let duration: Vec<char> = duration.chars().filter(|c| !c.is_whitespace()).collect();
-        let mut grouped_durations: Vec<(Vec<char>, Vec<char>)> = vec![(vec![], vec![])];
-        for i in 0..duration.len() {
-            // Vector initialised with a starting element so unwraps should never panic
-            if duration[i].is_numeric() {
-                grouped_durations.last_mut().unwrap().0.push(duration[i]);
-            } else {
-                grouped_durations.last_mut().unwrap().1.push(duration[i]);
-            }
-            if i != duration.len() - 1 && !duration[i].is_numeric() && duration[i + 1].is_numeric()
-            {
-                // move to next group
-                grouped_durations.push((vec![], vec![]));
-            }
-        }
-        if grouped_durations.is_empty() {
-            // `duration` either contains no numbers or no letters
-            return Err(Error::Format);
-        }
-        let mut total_duration = Duration::new(0, 0);
-        for (period, format) in grouped_durations {
-            let period = match period.iter().collect::<String>().parse::<u64>() {
-                Ok(period) => Ok(period),
-                Err(err) => Err(Error::ParseInt(err)),
-            }?;
-            let multiply_period = |multiplier: u32| -> std::result::Result<Duration, Self::Err> {
-                Duration::from_secs(period)
-                    .checked_mul(multiplier)
-                    .ok_or(Error::Overflow)
-            };
-            let period_duration = match format.iter().collect::<String>().as_ref() {
-                "ns" => Ok(Duration::from_nanos(period)),
-                "us" => Ok(Duration::from_micros(period)),
-                "ms" => Ok(Duration::from_millis(period)),
-                "s" => Ok(Duration::from_secs(period)),
-                "m" => multiply_period(MINUTE_IN_SECONDS),
-                "h" => multiply_period(HOUR_IN_SECONDS),
-                "d" => multiply_period(DAY_IN_SECONDS),
-                "w" => multiply_period(WEEK_IN_SECONDS),
-                "y" => multiply_period(YEAR_IN_SECONDS),
-                _ => Err(Error::Format),
-            }?;
-            total_duration = total_duration
-                .checked_add(period_duration)
-                .ok_or(Error::Overflow)?;
-        }
-        Ok(DurationString(total_duration))
+        DurationParser::new().parse(duration)
     }
 }
 
@@ -319,6 +639,207 @@ fn partial_cmp(&self, other: &DurationString) -> Option<std::cmp::Ordering> {
     }
 }
 
+/// Arithmetic that reports overflow instead of panicking like the operators do.
+///
+/// The right hand side can be a `DurationString` or a `Duration`.
+///
+/// ```rust
+/// use duration_string::DurationString;
+/// use std::time::Duration;
+///
+/// let deadline = DurationString::new(Duration::from_secs(5));
+/// assert_eq!(deadline.checked_sub(Duration::from_secs(10)), None);
+/// assert_eq!(deadline.saturating_sub(Duration::from_secs(10)), Duration::ZERO);
+/// ```
+impl DurationString {
+    /// Returns `None` if the result overflows, `.ok_or(Error::Overflow)` turns
+    /// it into a [`Result`].
+    #[must_use]
+    pub fn checked_add(self, other: impl Into<Duration>) -> Option<Self> {
+        self.0.checked_add(other.into()).map(Self::new)
+    }
+
+    /// Returns `None` if `other` is larger than `self`.
+    #[must_use]
+    pub fn checked_sub(self, other: impl Into<Duration>) -> Option<Self> {
+        self.0.checked_sub(other.into()).map(Self::new)
+    }
+
+    /// Returns `None` if the result overflows.
+    #[must_use]
+    pub fn checked_mul(self, other: u32) -> Option<Self> {
+        self.0.checked_mul(other).map(Self::new)
+    }
+
+    /// Returns `None` if `other` is zero.
+    #[must_use]
+    pub fn checked_div(self, other: u32) -> Option<Self> {
+        self.0.checked_div(other).map(Self::new)
+    }
+
+    /// Returns `Duration::MAX` if the result overflows.
+    #[must_use]
+    pub fn saturating_add(self, other: impl Into<Duration>) -> Self {
+        Self::new(self.0.saturating_add(other.into()))
+    }
+
+    /// Returns zero if `other` is larger than `self`.
+    #[must_use]
+    pub fn saturating_sub(self, other: impl Into<Duration>) -> Self {
+        Self::new(self.0.saturating_sub(other.into()))
+    }
+
+    /// Returns `Duration::MAX` if the result overflows.
+    #[must_use]
+    pub fn saturating_mul(self, other: u32) -> Self {
+        Self::new(self.0.saturating_mul(other))
+    }
+
+    /// The absolute difference between `self` and `other`, which never overflows.
+    #[must_use]
+    pub fn abs_diff(self, other: impl Into<Duration>) -> Self {
+        Self::new(self.0.abs_diff(other.into()))
+    }
+
+    /// Multiplies by a float, e.g. `1.5` for a backoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is negative or not finite, or if the result overflows.
+    #[must_use]
+    pub fn mul_f64(self, other: f64) -> Self {
+        Self::new(self.0.mul_f64(other))
+    }
+
+    /// Returns `None` if `other` is negative or NaN, or if the result overflows.
+    #[must_use]
+    pub fn checked_mul_f64(self, other: f64) -> Option<Self> {
+        Duration::try_from_secs_f64(self.0.as_secs_f64() * other)
+            .ok()
+            .map(Self::new)
+    }
+
+    /// Divides by a float, e.g. `2.0` to halve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is negative, zero or NaN, or if the result overflows.
+    #[must_use]
+    pub fn div_f64(self, other: f64) -> Self {
+        Self::new(self.0.div_f64(other))
+    }
+
+    /// Returns `None` if `other` is negative, zero or NaN, or if the result overflows.
+    #[must_use]
+    pub fn checked_div_f64(self, other: f64) -> Option<Self> {
+        Duration::try_from_secs_f64(self.0.as_secs_f64() / other)
+            .ok()
+            .map(Self::new)
+    }
+
+    /// The ratio between `self` and `other`, e.g. `0.5` for `30s` and `1m`.
+    ///
+    /// Dividing by zero returns infinity, or NaN if `self` is zero too.
+    #[must_use]
+    pub fn div_duration_f64(self, other: impl Into<Duration>) -> f64 {
+        self.0.div_duration_f64(other.into())
+    }
+
+    // `Mul<u64>` and `Div<u64>` are deliberately not implemented, a second
+    // integer impl next to `Mul<u32>` breaks type inference for `duration * 2`.
+
+    /// Multiplies by a `u64`, for factors that do not fit in a `u32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows, see [`DurationString::checked_mul_u64`].
+    #[must_use]
+    pub fn mul_u64(self, other: u64) -> Self {
+        self.checked_mul_u64(other)
+            .expect("overflow when multiplying duration by scalar")
+    }
+
+    /// Returns `None` if the result overflows.
+    #[must_use]
+    pub fn checked_mul_u64(self, other: u64) -> Option<Self> {
+        let nanos = self.0.as_nanos().checked_mul(u128::from(other))?;
+        parser::duration_from_nanos(nanos).ok().map(Self::new)
+    }
+
+    /// Divides by a `u64`, for divisors that do not fit in a `u32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero, see [`DurationString::checked_div_u64`].
+    #[must_use]
+    pub fn div_u64(self, other: u64) -> Self {
+        self.checked_div_u64(other)
+            .expect("divide by zero error when dividing duration by scalar")
+    }
+
+    /// Returns `None` if `other` is zero.
+    #[must_use]
+    pub fn checked_div_u64(self, other: u64) -> Option<Self> {
+        let nanos = self.0.as_nanos().checked_div(u128::from(other))?;
+        parser::duration_from_nanos(nanos).ok().map(Self::new)
+    }
+}
+
+/// Rounding to a multiple of a granularity, such as the nearest second before
+/// display.
+///
+/// The granularity can be a [`Unit`], a `DurationString` or a `Duration`. A
+/// granularity of zero leaves the duration as is.
+///
+/// ```rust
+/// use duration_string::{DurationString, Unit};
+/// use std::time::Duration;
+///
+/// let d = DurationString::new(Duration::from_millis(95_500));
+/// assert_eq!(String::from(d.round_to(Unit::Second).unwrap()), "1m36s");
+/// assert_eq!(String::from(d.floor_to(Unit::Minute)), "1m");
+/// assert_eq!(d.ceil_to(Duration::from_secs(30)).unwrap(), Duration::from_secs(120));
+/// ```
+impl DurationString {
+    /// Rounds down to a multiple of `granularity`.
+    #[must_use]
+    pub fn floor_to(self, granularity: impl Into<Duration>) -> Self {
+        // Rounding down never exceeds the duration, so it always fits
+        self.rounded_to(granularity.into(), RoundingMode::Floor)
+            .unwrap_or(self)
+    }
+
+    /// Rounds half up to the nearest multiple of `granularity`.
+    ///
+    /// Returns `None` if the result overflows.
+    #[must_use]
+    pub fn round_to(self, granularity: impl Into<Duration>) -> Option<Self> {
+        self.rounded_to(granularity.into(), RoundingMode::Nearest)
+    }
+
+    /// Rounds up to a multiple of `granularity`.
+    ///
+    /// Returns `None` if the result overflows.
+    #[must_use]
+    pub fn ceil_to(self, granularity: impl Into<Duration>) -> Option<Self> {
+        self.rounded_to(granularity.into(), RoundingMode::Ceil)
+    }
+
+    fn rounded_to(self, granularity: Duration, rounding: RoundingMode) -> Option<Self> {
+        let step = granularity.as_nanos();
+        if step == 0 {
+            return Some(self);
+        }
+        let ns = self.0.as_nanos();
+        let remainder = ns % step;
+        let mut nanos = ns - remainder;
+        if rounding.rounds_up(remainder, step) {
+            nanos = nanos.checked_add(step)?;
+        }
+        parser::duration_from_nanos(nanos).ok().map(Self::new)
+    }
+}
+
 impl Add for DurationString {
     type Output = Self;
 
@@ -361,6 +882,8 @@ fn add_assign(&mut self, other: DurationString) {
     }
 }
 
+// Panics on underflow, exactly like `Duration` itself, see `checked_sub`
+#[allow(clippy::unchecked_time_subtraction)]
 impl Sub for DurationString {
     type Output = Self;
 
@@ -369,6 +892,8 @@ fn sub(self, other: Self) -> Self::Output {
     }
 }
 
+// Panics on underflow, exactly like `Duration` itself, see `checked_sub`
+#[allow(clippy::unchecked_time_subtraction)]
 impl Sub<Duration> for DurationString {
     type Output = Self;
 
@@ -377,6 +902,8 @@ fn sub(self, other: Duration) -> Self::Output {
     }
 }
 
+// Panics on underflow, exactly like `Duration` itself, see `checked_sub`
+#[allow(clippy::unchecked_time_subtraction)]
 impl Sub<DurationString> for Duration {
     type Output = Self;
 
@@ -439,6 +966,58 @@ fn div_assign(&mut self, other: u32) {
     }
 }
 
+impl Mul<f64> for DurationString {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self::Output {
+        self.mul_f64(other)
+    }
+}
+
+impl Mul<DurationString> for f64 {
+    type Output = DurationString;
+
+    fn mul(self, other: DurationString) -> Self::Output {
+        other.mul_f64(self)
+    }
+}
+
+impl MulAssign<f64> for DurationString {
+    fn mul_assign(&mut self, other: f64) {
+        *self = self.mul_f64(other);
+    }
+}
+
+impl Div<f64> for DurationString {
+    type Output = Self;
+
+    fn div(self, other: f64) -> Self::Output {
+        self.div_f64(other)
+    }
+}
+
+impl DivAssign<f64> for DurationString {
+    fn div_assign(&mut self, other: f64) {
+        *self = self.div_f64(other);
+    }
+}
+
+impl Div for DurationString {
+    type Output = f64;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.div_duration_f64(other)
+    }
+}
+
+impl Div<Duration> for DurationString {
+    type Output = f64;
+
+    fn div(self, other: Duration) -> Self::Output {
+        self.div_duration_f64(other)
+    }
+}
+
 impl Sum for DurationString {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         Self::new(Duration::sum(iter.map(|duration_string| duration_string.0)))
@@ -468,7 +1047,7 @@ fn new() -> Self {
 }
 
 #[cfg(feature = "serde")]
-#[allow(clippy::needless_lifetimes)]
+#[allow(clippy::needless_lifetimes, clippy::elidable_lifetime_names)]
 impl<'de> serde::de::Visitor<'de> for DurationStringVisitor {
     type Value = DurationString;
 
@@ -510,6 +1089,7 @@ fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     }
 }
 #[cfg(test)]
+#[allow(clippy::duration_suboptimal_units, clippy::unchecked_time_subtraction)]
 mod tests {
     use super::*;
     #[cfg(feature = "serde")]
@@ -654,6 +1234,63 @@ fn test_from_string_y() {
         test_parse_string("1y", Duration::from_secs(31_556_926));
     }
 
+    #[test]
+    fn test_from_string_fraction_h() {
+        test_parse_string("1.5h", Duration::from_secs(5400));
+    }
+
+    #[test]
+    fn test_from_string_fraction_s() {
+        test_parse_string("0.25s", Duration::from_millis(250));
+        test_parse_string(".5s", Duration::from_millis(500));
+        test_parse_string("2.s", Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_from_string_fraction_y() {
+        test_parse_string("0.5y", Duration::from_secs(15_778_463));
+    }
+
+    #[test]
+    fn test_from_string_fraction_multiple() {
+        test_parse_string("1h 0.5m 1.000001ms", Duration::new(3630, 1_000_001));
+    }
+
+    #[test]
+    fn test_from_string_fraction_trailing_zeros() {
+        test_parse_string("1.500000000000000000000000s", Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_from_string_fraction_precision() {
+        assert_eq!(
+            "1.5ns".parse::<DurationString>(),
+            Err(Error::Precision { span: 0..3 })
+        );
+        assert_eq!(
+            "0.0000000001s".parse::<DurationString>(),
+            Err(Error::Precision { span: 0..12 })
+        );
+        assert_eq!(
+            "0.12345678901234567891y".parse::<DurationString>(),
+            Err(Error::Precision { span: 0..22 })
+        );
+    }
+
+    #[test]
+    fn test_from_string_fraction_invalid() {
+        assert_eq!(
+            "1.2.3s".parse::<DurationString>(),
+            Err(Error::Format {
+                span: 0..5,
+                token: String::from("1.2.3"),
+                expected: FORMAT,
+            })
+        );
+        ".s".parse::<DurationString>()
+            .expect_err("parsing \".s\" should fail");
+    }
+
     #[test]
     fn test_into_string_ms() {
         let d: String = DurationString::try_from(String::from("100ms"))
@@ -713,7 +1350,48 @@ fn test_into_string_overflow_unit() {
         let d: String = DurationString::try_from(String::from("61000ms"))
             .unwrap()
             .into();
-        assert_eq!(d, "61s");
+        assert_eq!(d, "1m1s");
+    }
+
+    #[test]
+    fn test_into_string_compound() {
+        let d: String = DurationString::new(Duration::from_secs(5415)).into();
+        assert_eq!(d, "1h30m15s");
+
+        let d: String = DurationString::new(Duration::new(90_061, 1_001_001)).into();
+        assert_eq!(d, "1d1h1m1s1ms1us1ns");
+
+        let d: String = DurationString::new(Duration::ZERO).into();
+        assert_eq!(d, "0s");
+    }
+
+    #[test]
+    fn test_into_string_round_trip() {
+        for duration in [
+            Duration::from_nanos(1),
+            Duration::from_secs(31_556_925),
+            Duration::from_secs(31_556_927),
+            Duration::new(604_799, 999_999_999),
+            Duration::MAX,
+        ] {
+            let s: String = DurationString::new(duration).into();
+            assert_eq!(
+                s.parse::<DurationString>(),
+                Ok(DurationString::new(duration))
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_single_unit_string() {
+        let d = DurationString::new(Duration::from_secs(5415));
+        assert_eq!(d.to_single_unit_string(), "5415s");
+
+        let d = DurationString::new(Duration::from_secs(61));
+        assert_eq!(d.to_single_unit_string(), "61s");
+
+        let d = DurationString::new(Duration::from_secs(120));
+        assert_eq!(d.to_single_unit_string(), "2m");
     }
 
     #[test]
@@ -722,6 +1400,32 @@ fn test_from_string_invalid_string() {
             .expect_err("Should have failed with invalid format");
     }
 
+    #[test]
+    fn test_error_diagnostic() {
+        let input = "1ä 2x";
+        let err = input.parse::<DurationString>().unwrap_err();
+        assert_eq!(err.span(), Some(1..3));
+        assert_eq!(
+            err.diagnostic(input).to_string(),
+            format!("{err}\n1ä 2x\n ^")
+        );
+        assert_eq!(
+            Error::Overflow.diagnostic(input).to_string(),
+            Error::Overflow.to_string()
+        );
+    }
+
+    #[test]
+    fn test_error_from_parse_int() {
+        let parse = |s: &str| -> Result<u64> { Ok(s.parse::<u64>()?) };
+        let err = parse("x").unwrap_err();
+        assert_eq!(err.span(), Some(0..0));
+        assert_eq!(
+            std::error::Error::source(&err).map(ToString::to_string),
+            Some(String::from("invalid digit found in string"))
+        );
+    }
+
     #[test]
     fn test_try_from_string_overflow_y() {
         let result = DurationString::try_from(String::from("584554530873y"));
@@ -895,6 +1599,207 @@ fn test_div_assign() {
         assert_eq!(duration_string_u32, result);
     }
 
+    #[test]
+    fn test_checked_add() {
+        let a = DurationString::new(Duration::from_secs(1));
+        let b = Duration::from_secs(2);
+        assert_eq!(
+            a.checked_add(b),
+            Some(DurationString::new(Duration::from_secs(3)))
+        );
+        assert_eq!(
+            a.checked_add(a),
+            Some(DurationString::new(Duration::from_secs(2)))
+        );
+        assert_eq!(DurationString::new(Duration::MAX).checked_add(a), None);
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        let a = DurationString::new(Duration::from_secs(3));
+        let b = Duration::from_secs(2);
+        assert_eq!(
+            a.checked_sub(b),
+            Some(DurationString::new(Duration::from_secs(1)))
+        );
+        assert_eq!(a.checked_sub(a), Some(DurationString::default()));
+        assert_eq!(DurationString::new(b).checked_sub(a), None);
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        let a = DurationString::new(Duration::from_secs(3));
+        assert_eq!(
+            a.checked_mul(2),
+            Some(DurationString::new(Duration::from_secs(6)))
+        );
+        assert_eq!(DurationString::new(Duration::MAX).checked_mul(2), None);
+    }
+
+    #[test]
+    fn test_checked_div() {
+        let a = DurationString::new(Duration::from_secs(6));
+        assert_eq!(
+            a.checked_div(2),
+            Some(DurationString::new(Duration::from_secs(3)))
+        );
+        assert_eq!(a.checked_div(0), None);
+    }
+
+    #[test]
+    fn test_saturating() {
+        let max = DurationString::new(Duration::MAX);
+        let a = DurationString::new(Duration::from_secs(1));
+        assert_eq!(max.saturating_add(a), max);
+        assert_eq!(
+            a.saturating_add(Duration::from_secs(1)),
+            Duration::from_secs(2)
+        );
+        assert_eq!(a.saturating_sub(max), DurationString::default());
+        assert_eq!(a.saturating_sub(a), DurationString::default());
+        assert_eq!(max.saturating_mul(2), max);
+        assert_eq!(a.saturating_mul(2), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        let a = DurationString::new(Duration::from_secs(1));
+        let b = DurationString::new(Duration::from_secs(3));
+        assert_eq!(a.abs_diff(b), Duration::from_secs(2));
+        assert_eq!(b.abs_diff(a), Duration::from_secs(2));
+        assert_eq!(a.abs_diff(Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_mul_f64() {
+        let a = DurationString::new(Duration::from_secs(2));
+        let result = DurationString::new(Duration::from_secs(3));
+        assert_eq!(a * 1.5, result);
+        assert_eq!(1.5 * a, result);
+        assert_eq!(a.mul_f64(1.5), result);
+        let mut a = a;
+        a *= 1.5;
+        assert_eq!(a, result);
+    }
+
+    #[test]
+    fn test_checked_mul_f64() {
+        let a = DurationString::new(Duration::from_secs(2));
+        assert_eq!(
+            a.checked_mul_f64(0.25),
+            Some(DurationString::new(Duration::from_millis(500)))
+        );
+        assert_eq!(a.checked_mul_f64(-1.0), None);
+        assert_eq!(a.checked_mul_f64(f64::NAN), None);
+        assert_eq!(a.checked_mul_f64(f64::INFINITY), None);
+        assert_eq!(
+            DurationString::new(Duration::MAX).checked_mul_f64(2.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_div_f64() {
+        let a = DurationString::new(Duration::from_secs(3));
+        let result = DurationString::new(Duration::from_millis(1500));
+        assert_eq!(a / 2.0, result);
+        assert_eq!(a.div_f64(2.0), result);
+        let mut a = a;
+        a /= 2.0;
+        assert_eq!(a, result);
+    }
+
+    #[test]
+    fn test_checked_div_f64() {
+        let a = DurationString::new(Duration::from_secs(3));
+        assert_eq!(
+            a.checked_div_f64(4.0),
+            Some(DurationString::new(Duration::from_millis(750)))
+        );
+        assert_eq!(a.checked_div_f64(0.0), None);
+        assert_eq!(a.checked_div_f64(-2.0), None);
+        assert_eq!(a.checked_div_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_div_duration() {
+        let a = DurationString::new(Duration::from_secs(30));
+        let b = DurationString::new(Duration::from_secs(60));
+        assert!((a / b - 0.5).abs() < f64::EPSILON);
+        assert!((b / Duration::from_secs(30) - 2.0).abs() < f64::EPSILON);
+        assert!(a.div_duration_f64(Duration::ZERO).is_infinite());
+        assert!(DurationString::default()
+            .div_duration_f64(Duration::ZERO)
+            .is_nan());
+    }
+
+    #[test]
+    fn test_mul_u64() {
+        let a = DurationString::new(Duration::from_nanos(3));
+        let factor = u64::from(u32::MAX) + 1;
+        assert_eq!(a.mul_u64(factor), Duration::from_nanos(3 * factor));
+        assert_eq!(a.checked_mul_u64(factor), Some(a.mul_u64(factor)));
+        assert_eq!(DurationString::new(Duration::MAX).checked_mul_u64(2), None);
+        // Integer literals still resolve to `u32`
+        assert_eq!(a * 2, Duration::from_nanos(6));
+    }
+
+    #[test]
+    fn test_div_u64() {
+        let a = DurationString::new(Duration::from_secs(u64::from(u32::MAX) + 1));
+        assert_eq!(a.div_u64(u64::from(u32::MAX) + 1), Duration::from_secs(1));
+        assert_eq!(a.checked_div_u64(0), None);
+        assert_eq!(a / 2, Duration::from_secs(1 << 31));
+    }
+
+    #[test]
+    fn test_floor_to() {
+        let d = DurationString::new(Duration::from_millis(95_500));
+        assert_eq!(d.floor_to(Unit::Minute), Duration::from_secs(60));
+        assert_eq!(d.floor_to(Unit::Second), Duration::from_secs(95));
+        assert_eq!(d.floor_to(Unit::Year), Duration::ZERO);
+        assert_eq!(d.floor_to(Duration::ZERO), d);
+        let max = DurationString::new(Duration::MAX);
+        assert_eq!(max.floor_to(Unit::Nanosecond), max);
+    }
+
+    #[test]
+    fn test_round_to() {
+        let d = DurationString::new(Duration::from_millis(95_500));
+        assert_eq!(
+            d.round_to(Unit::Second),
+            Some(DurationString::from(Duration::from_secs(96)))
+        );
+        assert_eq!(
+            d.round_to(Unit::Minute),
+            Some(DurationString::from(Duration::from_secs(120)))
+        );
+        let granularity = DurationString::new(Duration::from_secs(90));
+        assert_eq!(d.round_to(granularity), Some(granularity));
+        assert_eq!(
+            DurationString::new(Duration::MAX).round_to(Unit::Second),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ceil_to() {
+        let d = DurationString::new(Duration::new(1, 1));
+        assert_eq!(
+            d.ceil_to(Unit::Millisecond),
+            Some(DurationString::from(Duration::from_millis(1001)))
+        );
+        assert_eq!(d.ceil_to(Unit::Nanosecond), Some(d));
+        assert_eq!(
+            DurationString::new(Duration::MAX).ceil_to(Unit::Second),
+            None
+        );
+        assert_eq!(
+            DurationString::default().ceil_to(Unit::Year),
+            Some(DurationString::default())
+        );
+    }
+
     #[test]
     fn test_sum() {
         let durations = [
@@ -916,4 +1821,58 @@ fn test_sum() {
         assert_eq!(durations.iter().sum::<DurationString>(), result);
         assert_eq!(durations.into_iter().sum::<DurationString>(), result);
     }
+
+    #[test]
+    fn test_from_bytes() {
+        assert_eq!(
+            DurationString::from_bytes(b"1h 30m"),
+            Ok(DurationString::new(Duration::from_secs(5400)))
+        );
+        assert_eq!(
+            DurationString::from_bytes(b"1h 3\xff0m"),
+            Err(Error::InvalidUtf8 { span: 4..5 })
+        );
+        assert_eq!(
+            DurationString::from_bytes(b"5\xc2"),
+            Err(Error::InvalidUtf8 { span: 1..2 })
+        );
+        assert_eq!(DurationString::from_bytes(b""), Err(Error::Empty));
+    }
+
+    #[test]
+    fn test_from_str_const() {
+        const TIMEOUT: DurationString = DurationString::from_str_const("5m30s");
+        assert_eq!(TIMEOUT, Duration::from_secs(330));
+        assert_eq!(duration!("1.5h"), Duration::from_secs(5400));
+        for input in [
+            "1y 2w 3d 4h 5m 6s 7ms 8us 9ns",
+            "0.25h",
+            "3 \u{3bc}s",
+            "1h1m",
+            "1 0s",
+            "1.50000s 2 m s",
+        ] {
+            assert_eq!(
+                Ok(DurationString::from_str_const(input)),
+                input.parse::<DurationString>()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "macros")]
+    fn test_dur() {
+        const TIMEOUT: DurationString = dur!("5m30s");
+        assert_eq!(TIMEOUT, Duration::from_secs(330));
+        assert_eq!(
+            dur!("1 h 0.5 m"),
+            "1h 0.5m".parse::<DurationString>().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "duration literal is too large")]
+    fn test_from_str_const_overflow() {
+        let _ = DurationString::from_str_const("600000000000y");
+    }
 }
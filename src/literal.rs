@@ -0,0 +1,275 @@
+//! A `const fn` parser for duration literals such as `5m30s`.
+//!
+//! This module only depends on `unit.rs`. Both are copied into the procedural
+//! macro crate with `make sync-macros`, a test there checks they match.
+
+use crate::unit::UNITS;
+
+/// Why a literal does not fit the format, units carry their byte span.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LiteralError {
+    Empty,
+    MissingQuantity,
+    MissingUnit,
+    UnknownUnit(usize, usize),
+    DuplicateUnit(usize, usize),
+    Format,
+    Precision,
+    Overflow,
+    Clock,
+}
+
+impl LiteralError {
+    pub(crate) const fn message(self) -> &'static str {
+        match self {
+            Self::Empty => "empty duration literal",
+            Self::MissingQuantity => "missing quantity before unit in duration literal",
+            Self::MissingUnit => {
+                "missing unit in duration literal, expected ns, us, ms, s, m, h, d, w or y"
+            }
+            Self::UnknownUnit(..) => {
+                "unknown unit in duration literal, expected ns, us, ms, s, m, h, d, w or y"
+            }
+            Self::DuplicateUnit(..) => "duplicate unit in duration literal",
+            Self::Format => "invalid quantity in duration literal",
+            Self::Precision => "duration literal cannot be represented in whole nanoseconds",
+            Self::Overflow => "duration literal is too large",
+            Self::Clock => "clock notation is not supported in duration literals",
+        }
+    }
+}
+
+/// Whether the unit written as `s[span.0..span.1]` is `symbol`, ignoring
+/// whitespace within the name as in `m s`.
+const fn is_symbol(s: &[u8], span: (usize, usize), symbol: &[u8]) -> bool {
+    let (mut i, mut k) = (span.0, 0);
+    while i < span.1 {
+        if !s[i].is_ascii_whitespace() {
+            if k == symbol.len() || s[i] != symbol[k] {
+                return false;
+            }
+            k += 1;
+        }
+        i += 1;
+    }
+    k == symbol.len()
+}
+
+/// The index in `UNITS` of the unit written as `s[span.0..span.1]`.
+const fn unit_at(s: &[u8], span: (usize, usize)) -> Option<usize> {
+    let mut u = 0;
+    while u < UNITS.len() {
+        let symbols = UNITS[u].symbols();
+        let mut k = 0;
+        while k < symbols.len() {
+            if is_symbol(s, span, symbols[k].as_bytes()) {
+                return Some(u);
+            }
+            k += 1;
+        }
+        u += 1;
+    }
+    None
+}
+
+/// A `<quantity><unit>` pair, accumulated one byte at a time like the runtime
+/// parser's `Component`.
+struct Component {
+    integer: u128,
+    integer_digits: u32,
+    dots: u32,
+    /// The fraction up to its last non-zero digit.
+    fraction: u128,
+    fraction_digits: u32,
+    significant_digits: u32,
+    unit: Option<(usize, usize)>,
+}
+
+impl Component {
+    const fn new() -> Self {
+        Self {
+            integer: 0,
+            integer_digits: 0,
+            dots: 0,
+            fraction: 0,
+            fraction_digits: 0,
+            significant_digits: 0,
+            unit: None,
+        }
+    }
+
+    const fn has_quantity(&self) -> bool {
+        self.integer_digits > 0 || self.dots > 0
+    }
+
+    /// Adds the ASCII digit or decimal point `b`.
+    const fn push(&mut self, b: u8) -> Result<(), LiteralError> {
+        if b == b'.' {
+            self.dots += 1;
+            return Ok(());
+        }
+        // Saturates at a value beyond `u64::MAX`, which is rejected in `in_nano`
+        let digit = (b - b'0') as u128;
+        match self.dots {
+            0 => {
+                self.integer_digits += 1;
+                if self.integer <= u64::MAX as u128 {
+                    self.integer = self.integer * 10 + digit;
+                }
+            }
+            1 => {
+                self.fraction_digits += 1;
+                // Trailing zeros carry no precision, they only count once
+                // followed by another digit
+                if digit != 0 {
+                    if self.fraction_digits > 19 {
+                        return Err(LiteralError::Precision);
+                    }
+                    let shift = 10u128.pow(self.fraction_digits - self.significant_digits);
+                    self.fraction = self.fraction * shift + digit;
+                    self.significant_digits = self.fraction_digits;
+                }
+            }
+            // More than one dot is rejected by `in_nano`
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Checks the component in the order of the runtime parser, `seen` has a
+    /// bit set for each unit already used.
+    const fn in_nano(&self, s: &[u8], seen: &mut u16) -> Result<u128, LiteralError> {
+        let Some(span) = self.unit else {
+            return Err(LiteralError::MissingUnit);
+        };
+        let Some(u) = unit_at(s, span) else {
+            return Err(LiteralError::UnknownUnit(span.0, span.1));
+        };
+        if !self.has_quantity() {
+            return Err(LiteralError::MissingQuantity);
+        }
+        let bit = 1 << u;
+        if *seen & bit != 0 {
+            return Err(LiteralError::DuplicateUnit(span.0, span.1));
+        }
+        *seen |= bit;
+        if self.dots > 1 || (self.integer_digits == 0 && self.fraction_digits == 0) {
+            return Err(LiteralError::Format);
+        }
+        if self.integer > u64::MAX as u128 {
+            return Err(LiteralError::Overflow);
+        }
+        let unit_in_nano = UNITS[u].as_nanos();
+        let Some(nanos) = self.integer.checked_mul(unit_in_nano) else {
+            return Err(LiteralError::Overflow);
+        };
+        let scale = 10u128.pow(self.significant_digits);
+        let fraction = self.fraction * unit_in_nano;
+        if !fraction.is_multiple_of(scale) {
+            return Err(LiteralError::Precision);
+        }
+        match nanos.checked_add(fraction / scale) {
+            Some(nanos) => Ok(nanos),
+            None => Err(LiteralError::Overflow),
+        }
+    }
+}
+
+/// Parses `s` into nanoseconds with the grammar of the default
+/// `DurationParser`, except that clock notation is not supported and only
+/// ASCII whitespace is ignored.
+pub(crate) const fn parse_literal(s: &[u8]) -> Result<u128, LiteralError> {
+    let mut total: u128 = 0;
+    let mut seen: u16 = 0;
+    let mut component: Option<Component> = None;
+    let mut i = 0;
+    while i < s.len() {
+        let b = s[i];
+        i += 1;
+        if b.is_ascii_whitespace() {
+            continue;
+        }
+        if b == b':' {
+            return Err(LiteralError::Clock);
+        }
+        if b.is_ascii_digit() || b == b'.' {
+            if let Some(done) = &component {
+                if done.unit.is_some() {
+                    total = match done.in_nano(s, &mut seen) {
+                        Ok(nanos) => match total.checked_add(nanos) {
+                            Some(total) => total,
+                            None => return Err(LiteralError::Overflow),
+                        },
+                        Err(err) => return Err(err),
+                    };
+                    component = None;
+                }
+            }
+            if component.is_none() {
+                component = Some(Component::new());
+            }
+            if let Some(current) = &mut component {
+                if let Err(err) = current.push(b) {
+                    return Err(err);
+                }
+            }
+        } else {
+            if component.is_none() {
+                component = Some(Component::new());
+            }
+            if let Some(current) = &mut component {
+                current.unit = match current.unit {
+                    Some((start, _)) => Some((start, i)),
+                    None => Some((i - 1, i)),
+                };
+            }
+        }
+    }
+    let Some(last) = &component else {
+        return Err(LiteralError::Empty);
+    };
+    match last.in_nano(s, &mut seen) {
+        Ok(nanos) => match total.checked_add(nanos) {
+            Some(total) => Ok(total),
+            None => Err(LiteralError::Overflow),
+        },
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(s: &str) -> Result<u128, LiteralError> {
+        parse_literal(s.as_bytes())
+    }
+
+    #[test]
+    fn test_parse_literal() {
+        assert_eq!(literal("5m30s"), Ok(330_000_000_000));
+        assert_eq!(literal(" 1h 30m "), Ok(5_400_000_000_000));
+        assert_eq!(literal("1.5s"), Ok(1_500_000_000));
+        assert_eq!(literal(".5ms 1 0 \u{b5}s"), Ok(510_000));
+        assert_eq!(literal("1 m s"), Ok(1_000_000));
+        assert_eq!(literal("1.000000000000000000000000s"), Ok(1_000_000_000));
+    }
+
+    #[test]
+    fn test_parse_literal_errors() {
+        assert_eq!(literal(" "), Err(LiteralError::Empty));
+        assert_eq!(literal("5"), Err(LiteralError::MissingUnit));
+        assert_eq!(literal("ms"), Err(LiteralError::MissingQuantity));
+        assert_eq!(literal("5x"), Err(LiteralError::UnknownUnit(1, 2)));
+        assert_eq!(literal("5 M "), Err(LiteralError::UnknownUnit(2, 3)));
+        assert_eq!(literal("1:30"), Err(LiteralError::Clock));
+        assert_eq!(literal("1s 2s"), Err(LiteralError::DuplicateUnit(4, 5)));
+        assert_eq!(literal("1.2.3s"), Err(LiteralError::Format));
+        assert_eq!(literal(".s"), Err(LiteralError::Format));
+        assert_eq!(literal("1.5ns"), Err(LiteralError::Precision));
+        assert_eq!(
+            literal("999999999999999999999999999999999999999y"),
+            Err(LiteralError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,883 @@
+use crate::clock::parse_clock;
+use crate::digit::decimal_value;
+use crate::unit::SECOND_IN_NANO;
+use crate::{
+    expected_units, CustomUnits, DurationString, Error, Result, SignedDurationString, Unit,
+    UnitSystem, FORMAT,
+};
+use std::convert::TryFrom;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::time::Duration;
+
+/// A quantity such as `15` or `1.25`, accumulated one character at a time.
+#[derive(Default)]
+struct Quantity {
+    integer: u64,
+    integer_digits: usize,
+    integer_overflow: bool,
+    dots: usize,
+    /// The fraction up to its last non-zero digit, if that is at most 19 digits.
+    fraction: u64,
+    fraction_digits: u32,
+    significant_digits: u32,
+}
+
+impl Quantity {
+    /// Adds the ASCII digit or decimal point `c`.
+    fn push(&mut self, c: char) {
+        let Some(digit) = c.to_digit(10) else {
+            self.dots += 1;
+            return;
+        };
+        match self.dots {
+            0 => {
+                self.integer_digits += 1;
+                match self
+                    .integer
+                    .checked_mul(10)
+                    .and_then(|integer| integer.checked_add(u64::from(digit)))
+                {
+                    Some(integer) => self.integer = integer,
+                    None => self.integer_overflow = true,
+                }
+            }
+            1 => {
+                self.fraction_digits = self.fraction_digits.saturating_add(1);
+                // Trailing zeros carry no precision, they only count once
+                // followed by another digit
+                if digit != 0 {
+                    if self.fraction_digits <= 19 {
+                        let shift = 10u64.pow(self.fraction_digits - self.significant_digits);
+                        self.fraction = self.fraction * shift + u64::from(digit);
+                    }
+                    self.significant_digits = self.fraction_digits;
+                }
+            }
+            // More than one dot is rejected by `in_nano`
+            _ => {}
+        }
+    }
+
+    /// Converts the quantity of a unit into nanoseconds.
+    ///
+    /// The fraction is applied with integer arithmetic, so the result is exact
+    /// or an [`Error::Precision`] is returned.
+    fn in_nano(
+        &self,
+        span: Range<usize>,
+        unit_in_nano: u128,
+        token: impl FnOnce() -> String,
+        expected: &'static str,
+    ) -> Result<u128> {
+        if self.dots > 1
+            || (self.dots == 1 && self.integer_digits == 0 && self.fraction_digits == 0)
+        {
+            return Err(Error::Format {
+                token: token(),
+                span,
+                expected,
+            });
+        }
+        if self.integer_overflow {
+            return Err(Error::ParseInt {
+                span,
+                source: integer_overflow(),
+            });
+        }
+        let nanos = u128::from(self.integer)
+            .checked_mul(unit_in_nano)
+            .ok_or(Error::Overflow)?;
+        if self.significant_digits == 0 {
+            return Ok(nanos);
+        }
+        // No unit is fine grained enough to represent more than 19
+        // significant fractional digits exactly.
+        if self.significant_digits > 19 {
+            return Err(Error::Precision { span });
+        }
+        let scale = 10u128.pow(self.significant_digits);
+        // `fraction * unit_in_nano` can exceed a `u128` for custom units, the
+        // unit is split so both products stay below `unit_in_nano` or 10^38
+        let fraction = u128::from(self.fraction);
+        let remainder = fraction * (unit_in_nano % scale);
+        if !remainder.is_multiple_of(scale) {
+            return Err(Error::Precision { span });
+        }
+        nanos
+            .checked_add(fraction * (unit_in_nano / scale) + remainder / scale)
+            .ok_or(Error::Overflow)
+    }
+}
+
+/// The error of parsing an integer quantity that does not fit in a `u64`.
+fn integer_overflow() -> ParseIntError {
+    // Every integer beyond `u64::MAX` fails with the same error
+    "18446744073709551616".parse::<u64>().unwrap_err()
+}
+
+/// A `<quantity><unit>` pair such as `15m`, either side may be empty.
+///
+/// The spans cover the first to the last character of each side, including
+/// any whitespace in between.
+struct Component {
+    quantity: Quantity,
+    quantity_span: Range<usize>,
+    unit_span: Range<usize>,
+}
+
+impl Component {
+    fn new(at: usize) -> Self {
+        Self {
+            quantity: Quantity::default(),
+            quantity_span: at..at,
+            unit_span: at..at,
+        }
+    }
+}
+
+/// A unit found by its name.
+enum FoundUnit {
+    Unit(Unit),
+    /// The index of a custom unit.
+    Custom(usize),
+}
+
+/// The units a duration already used.
+struct Seen {
+    units: u16,
+    custom: Vec<bool>,
+}
+
+impl Seen {
+    /// Marks `unit` as used, returning whether it was used before.
+    fn insert(&mut self, unit: &FoundUnit) -> bool {
+        match *unit {
+            FoundUnit::Unit(unit) => {
+                let bit = 1 << (unit as u16);
+                let used = self.units & bit != 0;
+                self.units |= bit;
+                used
+            }
+            FoundUnit::Custom(i) => std::mem::replace(&mut self.custom[i], true),
+        }
+    }
+}
+
+/// Grows `span` to end with the character at `c`.
+fn extend(span: &mut Range<usize>, c: &Range<usize>) {
+    if span.start == span.end {
+        span.start = c.start;
+    }
+    span.end = c.end;
+}
+
+/// Configurable parsing of the `[0-9]+(.[0-9]+)?(ns|us|ms|[smhdwy])` format.
+///
+/// The default parser is the one used by `FromStr for DurationString`.
+///
+/// ```rust
+/// use duration_string::{DurationParser, Error};
+/// use std::time::Duration;
+///
+/// let parser = DurationParser::new().strict(true);
+/// assert_eq!(parser.parse("1h 30m").unwrap(), Duration::from_secs(5400));
+/// assert_eq!(
+///     parser.parse("1 0s"),
+///     Err(Error::UnexpectedWhitespace { span: 1..2 })
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[allow(clippy::struct_excessive_bools)] // Independent builder options
+pub struct DurationParser {
+    pub(crate) strict: bool,
+    unicode_digits: bool,
+    aliases: bool,
+    case_insensitive: bool,
+    unit_system: UnitSystem,
+    custom_units: CustomUnits,
+}
+
+impl DurationParser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only allow whitespace between complete `<quantity><unit>` components.
+    ///
+    /// By default whitespace is ignored anywhere, so `1 0s` is ten seconds. In
+    /// strict mode it is rejected with [`Error::UnexpectedWhitespace`], as is
+    /// `1 h`. Leading and trailing whitespace is allowed in both modes.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept any Unicode decimal digit, such as `٣` or `３`, as its ASCII equivalent.
+    ///
+    /// By default only ASCII digits are accepted and any other numeric
+    /// character is rejected with [`Error::NonAsciiDigit`]. Numeric characters
+    /// that are not decimal digits, such as `²` or `Ⅳ`, are rejected in both modes.
+    #[must_use]
+    pub fn unicode_digits(mut self, unicode_digits: bool) -> Self {
+        self.unicode_digits = unicode_digits;
+        self
+    }
+
+    /// Lenient mode, also accept the long-form names and abbreviations of
+    /// [`Unit::aliases`] such as `5 minutes`, `2hrs` or `30sec`.
+    #[must_use]
+    pub fn aliases(mut self, aliases: bool) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Match units regardless of ASCII case, e.g. `5MS` or `2 Hours`.
+    #[must_use]
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// The length of a year and whether months are accepted, see [`UnitSystem`].
+    #[must_use]
+    pub fn unit_system(mut self, unit_system: UnitSystem) -> Self {
+        self.unit_system = unit_system;
+        self
+    }
+
+    /// Also accept the units of `custom_units`.
+    #[must_use]
+    pub fn custom_units(mut self, custom_units: CustomUnits) -> Self {
+        self.custom_units = custom_units;
+        self
+    }
+
+    /// Parses `input` with the configured options.
+    ///
+    /// Input containing `:` is parsed as clock notation instead, such as
+    /// `01:30:00`, `90:00` or `2d 03:00:00.250`. The digits and the day
+    /// prefix follow the options, while the `HH:MM:SS` part never contains
+    /// whitespace and the unit system and custom units do not apply to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] describing where `input` does not fit the format,
+    /// or [`Error::Overflow`] if the total does not fit in a `Duration`.
+    pub fn parse(&self, input: &str) -> Result<DurationString> {
+        if input.trim().is_empty() {
+            return Err(Error::Empty);
+        }
+        if input.contains(':') {
+            return parse_clock(self, input);
+        }
+        // A single pass without allocations unless there are custom units,
+        // each component is checked as soon as the next one starts
+        let mut seen = Seen {
+            units: 0,
+            custom: vec![false; self.custom_units.units().len()],
+        };
+        let mut total_nanos: u128 = 0;
+        let mut component: Option<Component> = None;
+        let mut in_unit = true;
+        let mut whitespace: Option<usize> = None;
+        for (i, c) in input.char_indices() {
+            if c.is_whitespace() {
+                if component.is_some() {
+                    whitespace.get_or_insert(i);
+                }
+                continue;
+            }
+            let span = i..i + c.len_utf8();
+            let quantity = self.quantity_char(c, &span)?;
+            if let Some(start) = whitespace.take() {
+                // Only whitespace between a unit and the next quantity is allowed
+                if self.strict && !(in_unit && quantity.is_some()) {
+                    return Err(Error::UnexpectedWhitespace { span: start..i });
+                }
+            }
+            if let Some(q) = quantity {
+                if in_unit {
+                    if let Some(done) = component.replace(Component::new(i)) {
+                        total_nanos = total_nanos
+                            .checked_add(self.component_in_nano(input, &done, &mut seen)?)
+                            .ok_or(Error::Overflow)?;
+                    }
+                    in_unit = false;
+                }
+                let current = component.get_or_insert_with(|| Component::new(i));
+                current.quantity.push(q);
+                extend(&mut current.quantity_span, &span);
+            } else {
+                let current = component.get_or_insert_with(|| Component::new(i));
+                in_unit = true;
+                extend(&mut current.unit_span, &span);
+            }
+        }
+        if let Some(last) = component {
+            total_nanos = total_nanos
+                .checked_add(self.component_in_nano(input, &last, &mut seen)?)
+                .ok_or(Error::Overflow)?;
+        }
+        duration_from_nanos(total_nanos).map(DurationString::new)
+    }
+
+    /// Parses `input` with an optional leading `+` or `-` sign, e.g. `-5m`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`DurationParser::parse`], with spans
+    /// relative to the whole `input`.
+    pub fn parse_signed(&self, input: &str) -> Result<SignedDurationString> {
+        let trimmed = input.trim_start();
+        let (negative, rest) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..]),
+            Some(b'+') => (false, &trimmed[1..]),
+            _ => (false, trimmed),
+        };
+        let offset = input.len() - rest.len();
+        let magnitude = self.parse(rest).map_err(|err| err.offset(offset))?;
+        Ok(SignedDurationString::new(negative, magnitude.into()))
+    }
+
+    /// Checks a complete component, `seen` holds the units already used.
+    fn component_in_nano(
+        &self,
+        input: &str,
+        component: &Component,
+        seen: &mut Seen,
+    ) -> Result<u128> {
+        let Component {
+            quantity,
+            quantity_span,
+            unit_span,
+        } = component;
+        if unit_span.is_empty() {
+            return Err(Error::MissingUnit {
+                span: quantity_span.end..quantity_span.end,
+                expected: self.expected_units(),
+            });
+        }
+        let name = &input[unit_span.clone()];
+        let Some(unit) = self.unit(name) else {
+            return Err(Error::UnknownUnit {
+                unit: name.to_string(),
+                span: unit_span.clone(),
+                expected: self.expected_units(),
+            });
+        };
+        if quantity_span.is_empty() {
+            return Err(Error::MissingQuantity {
+                span: unit_span.start..unit_span.start,
+            });
+        }
+        if seen.insert(&unit) {
+            return Err(Error::DuplicateUnit {
+                unit: name.to_string(),
+                span: unit_span.clone(),
+            });
+        }
+        let unit_in_nano = match unit {
+            FoundUnit::Unit(unit) => self.unit_system.unit_in_nano(unit),
+            FoundUnit::Custom(i) => self.custom_units.units()[i].nanos,
+        };
+        quantity.in_nano(
+            quantity_span.clone(),
+            unit_in_nano,
+            || {
+                // The quantity as it was parsed, for the error
+                input[quantity_span.clone()]
+                    .chars()
+                    .filter_map(|c| self.quantity_char(c, &(0..0)).ok().flatten())
+                    .collect()
+            },
+            FORMAT,
+        )
+    }
+
+    /// Looks up a unit by its symbol, or by an alias in lenient mode, before
+    /// the custom units.
+    ///
+    /// Whitespace within `name` is ignored, as in `m s`.
+    fn unit(&self, name: &str) -> Option<FoundUnit> {
+        let compact = !name.contains(char::is_whitespace);
+        let eq = |a: char, b: char| {
+            if self.case_insensitive {
+                a.eq_ignore_ascii_case(&b)
+            } else {
+                a == b
+            }
+        };
+        let matches = |candidate: &&str| {
+            if compact && self.case_insensitive {
+                return candidate.eq_ignore_ascii_case(name);
+            }
+            if compact {
+                return *candidate == name;
+            }
+            let mut chars = name.chars().filter(|c| !c.is_whitespace());
+            candidate
+                .chars()
+                .all(|c| chars.next().is_some_and(|n| eq(c, n)))
+                && chars.next().is_none()
+        };
+        let unit = self.unit_system.units().iter().copied().find(|unit| {
+            unit.symbols().iter().any(matches)
+                || (self.aliases && unit.aliases().iter().any(matches))
+        });
+        if let Some(unit) = unit {
+            return Some(FoundUnit::Unit(unit));
+        }
+        self.custom_units
+            .units()
+            .iter()
+            .position(|unit| unit.symbols.iter().map(String::as_str).any(|s| matches(&s)))
+            .map(FoundUnit::Custom)
+    }
+
+    /// Whether `name` is written as `unit` for this parser.
+    pub(crate) fn is_unit(&self, name: &str, unit: Unit) -> bool {
+        matches!(self.unit(name), Some(FoundUnit::Unit(found)) if found == unit)
+    }
+
+    /// Every unit name the parser accepts, for errors.
+    pub(crate) fn expected_units(&self) -> Vec<String> {
+        let builtin = self.unit_system.units().iter().rev().flat_map(|unit| {
+            let aliases: &[&str] = if self.aliases { unit.aliases() } else { &[] };
+            unit.symbols().iter().chain(aliases)
+        });
+        let custom = self
+            .custom_units
+            .units()
+            .iter()
+            .rev()
+            .flat_map(|unit| unit.symbols.iter().map(String::as_str));
+        expected_units(builtin.copied().chain(custom))
+    }
+
+    /// The ASCII digit or decimal point `c` stands for, if it is part of a quantity.
+    pub(crate) fn quantity_char(&self, c: char, span: &Range<usize>) -> Result<Option<char>> {
+        if c.is_ascii_digit() || c == '.' {
+            return Ok(Some(c));
+        }
+        if !c.is_numeric() {
+            return Ok(None);
+        }
+        match decimal_value(c).filter(|_| self.unicode_digits) {
+            Some(value) => Ok(char::from_digit(value, 10)),
+            None => Err(Error::NonAsciiDigit {
+                span: span.clone(),
+                digit: c,
+            }),
+        }
+    }
+}
+
+/// Converts a quantity such as `15` or `1.25` of a unit into nanoseconds, see
+/// [`Quantity::in_nano`]. `quantity` only holds ASCII digits and dots, an
+/// invalid one is an [`Error::Format`] of the `expected` format.
+pub(crate) fn quantity_in_nano(
+    quantity: &str,
+    span: Range<usize>,
+    unit_in_nano: u128,
+    expected: &'static str,
+) -> Result<u128> {
+    let mut accumulated = Quantity::default();
+    quantity.chars().for_each(|c| accumulated.push(c));
+    accumulated.in_nano(span, unit_in_nano, || quantity.to_string(), expected)
+}
+
+pub(crate) fn duration_from_nanos(nanos: u128) -> Result<Duration> {
+    let secs = u64::try_from(nanos / SECOND_IN_NANO).map_err(|_| Error::Overflow)?;
+    // The remainder is below one second and always fits in a `u32`
+    let nanos = u32::try_from(nanos % SECOND_IN_NANO).map_err(|_| Error::Overflow)?;
+    Ok(Duration::new(secs, nanos))
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use crate::YearLength;
+
+    fn parse(input: &str) -> Result<Duration> {
+        DurationParser::new().parse(input).map(Duration::from)
+    }
+
+    fn parse_strict(input: &str) -> Result<Duration> {
+        DurationParser::new()
+            .strict(true)
+            .parse(input)
+            .map(Duration::from)
+    }
+
+    fn span(input: &str) -> Option<Range<usize>> {
+        parse(input).unwrap_err().span()
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(parse(""), Err(Error::Empty));
+        assert_eq!(parse(" \t\n"), Err(Error::Empty));
+    }
+
+    #[test]
+    fn test_unknown_unit() {
+        assert_eq!(
+            parse("5m 3x 2s"),
+            Err(Error::UnknownUnit {
+                span: 4..5,
+                unit: String::from("x"),
+                expected: DurationParser::new().expected_units(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_unit_with_whitespace() {
+        assert_eq!(
+            parse("1 h 3 m x 1s"),
+            Err(Error::UnknownUnit {
+                span: 6..9,
+                unit: String::from("m x"),
+                expected: DurationParser::new().expected_units(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_unit() {
+        let expected = DurationParser::new().expected_units();
+        assert_eq!(
+            parse("1234"),
+            Err(Error::MissingUnit {
+                span: 4..4,
+                expected: expected.clone(),
+            })
+        );
+        assert_eq!(
+            parse("1h 30 "),
+            Err(Error::MissingUnit {
+                span: 5..5,
+                expected,
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_quantity() {
+        assert_eq!(parse("ms"), Err(Error::MissingQuantity { span: 0..0 }));
+        assert_eq!(parse(" ms 1h"), Err(Error::MissingQuantity { span: 1..1 }));
+    }
+
+    #[test]
+    fn test_duplicate_unit() {
+        assert_eq!(
+            parse("1h 30m 2h"),
+            Err(Error::DuplicateUnit {
+                span: 8..9,
+                unit: String::from("h"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_integer_too_large() {
+        let err = parse("18446744073709551616s").unwrap_err();
+        assert!(matches!(err, Error::ParseInt { span, .. } if span == (0..20)));
+    }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(parse_strict("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_strict(" 1h \t 30m\n"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_strict("1.5s 2ms"), Ok(Duration::from_millis(1502)));
+    }
+
+    #[test]
+    fn test_strict_whitespace_in_quantity() {
+        assert_eq!(parse("1 0s"), Ok(Duration::from_secs(10)));
+        assert_eq!(
+            parse_strict("1 0s"),
+            Err(Error::UnexpectedWhitespace { span: 1..2 })
+        );
+        assert_eq!(
+            parse_strict("1h 3 0m"),
+            Err(Error::UnexpectedWhitespace { span: 4..5 })
+        );
+    }
+
+    #[test]
+    fn test_strict_whitespace_before_unit() {
+        assert_eq!(parse("1 h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(
+            parse_strict("1  h"),
+            Err(Error::UnexpectedWhitespace { span: 1..3 })
+        );
+    }
+
+    #[test]
+    fn test_strict_whitespace_in_unit() {
+        assert_eq!(parse("1m s"), Ok(Duration::from_millis(1)));
+        assert_eq!(
+            parse_strict("1m s"),
+            Err(Error::UnexpectedWhitespace { span: 2..3 })
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_digit() {
+        assert_eq!(
+            parse("1h \u{0663}m"),
+            Err(Error::NonAsciiDigit {
+                span: 3..5,
+                digit: '\u{0663}',
+            })
+        );
+        assert_eq!(
+            parse("\u{00b2}s"),
+            Err(Error::NonAsciiDigit {
+                span: 0..2,
+                digit: '\u{00b2}',
+            })
+        );
+    }
+
+    #[test]
+    fn test_unicode_digits() {
+        let parser = DurationParser::new().unicode_digits(true);
+        assert_eq!(
+            parser.parse("\u{0661}\u{0665}m").map(Duration::from),
+            Ok(Duration::from_secs(900))
+        );
+        assert_eq!(
+            parser.parse("\u{ff11}.\u{ff15}s 5ms").map(Duration::from),
+            Ok(Duration::from_millis(1505))
+        );
+        assert_eq!(
+            parser.parse("2\u{00b2}s"),
+            Err(Error::NonAsciiDigit {
+                span: 1..3,
+                digit: '\u{00b2}',
+            })
+        );
+    }
+
+    #[test]
+    fn test_aliases() {
+        let parser = DurationParser::new().aliases(true);
+        let parse = |input| parser.parse(input).map(Duration::from);
+        assert_eq!(parse("5 minutes"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse("2hrs 30sec"), Ok(Duration::from_secs(7230)));
+        assert_eq!(parse("1 day 1 hour"), Ok(Duration::from_secs(90_000)));
+        assert_eq!(parse("3 secs 5ms"), Ok(Duration::from_millis(3005)));
+        assert_eq!(parse("1.5 weeks"), Ok(Duration::from_secs(907_200)));
+        assert_eq!(parse("10 nanos"), Ok(Duration::from_nanos(10)));
+        assert_eq!(parse("1 yr"), Ok(Duration::from_secs(31_556_926)));
+    }
+
+    #[test]
+    fn test_aliases_disabled_by_default() {
+        assert_eq!(
+            parse("5 minutes"),
+            Err(Error::UnknownUnit {
+                span: 2..9,
+                unit: String::from("minutes"),
+                expected: DurationParser::new().expected_units(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_aliases_duplicate_unit() {
+        let parser = DurationParser::new().aliases(true);
+        assert_eq!(
+            parser.parse("1m 2 minutes"),
+            Err(Error::DuplicateUnit {
+                span: 5..12,
+                unit: String::from("minutes"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let parser = DurationParser::new().case_insensitive(true);
+        let parse = |input| parser.parse(input).map(Duration::from);
+        assert_eq!(parse("1H 30M"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse("5MS"), Ok(Duration::from_millis(5)));
+        assert!(matches!(parse("2 Hours"), Err(Error::UnknownUnit { .. })));
+
+        let parser = parser.aliases(true);
+        assert_eq!(
+            parser.parse("2 Hours").map(Duration::from),
+            Ok(Duration::from_secs(7200))
+        );
+        assert!(matches!(
+            DurationParser::new().parse("1H"),
+            Err(Error::UnknownUnit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unit_system() {
+        let system = UnitSystem::new().year(YearLength::Julian);
+        let parser = DurationParser::new().unit_system(system);
+        let parse = |input| parser.parse(input).map(Duration::from);
+        assert_eq!(parse("1y"), Ok(Duration::from_secs(31_557_600)));
+        assert_eq!(
+            parse("1mo"),
+            Err(Error::UnknownUnit {
+                span: 1..3,
+                unit: String::from("mo"),
+                expected: parser.expected_units(),
+            })
+        );
+
+        let parser = parser.unit_system(system.months(true)).aliases(true);
+        let parse = |input| parser.parse(input).map(Duration::from);
+        assert_eq!(parse("1mo"), Ok(Duration::from_secs(2_629_800)));
+        assert_eq!(parse("1y 2 months"), Ok(Duration::from_secs(36_817_200)));
+        assert_eq!(parse("1m"), Ok(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_custom_units() {
+        let units = CustomUnits::new()
+            .unit(&["tick", "ticks"], Duration::from_millis(50))
+            .unit(&["epoch", "epochs"], Duration::from_secs(384))
+            .unit(&["slot", "slots"], Duration::from_secs(12));
+        let parser = DurationParser::new().custom_units(units);
+        let parse = |input| parser.parse(input).map(Duration::from);
+        assert_eq!(parse("3 epochs 2 slots"), Ok(Duration::from_secs(1176)));
+        assert_eq!(
+            parse("1.5epoch 1m 3tick"),
+            Ok(Duration::from_millis(636_150))
+        );
+        assert_eq!(
+            parse("1 slot 2 slots"),
+            Err(Error::DuplicateUnit {
+                span: 9..14,
+                unit: String::from("slots"),
+            })
+        );
+        assert_eq!(
+            parse("1 fortnight"),
+            Err(Error::UnknownUnit {
+                span: 2..11,
+                unit: String::from("fortnight"),
+                expected: parser.expected_units(),
+            })
+        );
+        assert_eq!(
+            parser.clone().case_insensitive(true).parse("2 SLOTS"),
+            Ok(DurationString::new(Duration::from_secs(24)))
+        );
+
+        let parser = DurationParser::new()
+            .custom_units(CustomUnits::new().unit(&["eon"], Duration::from_secs(u64::MAX)));
+        assert_eq!(
+            parser.parse("1.1234567890123456789eon"),
+            Err(Error::Precision { span: 0..21 })
+        );
+        assert_eq!(parser.parse("1.5eon"), Err(Error::Overflow));
+        assert_eq!(
+            parser.parse("0.5eon").map(Duration::from),
+            Ok(Duration::new(u64::MAX / 2, 500_000_000))
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let display = |parser: &DurationParser, input| parser.parse(input).unwrap_err().to_string();
+        let parser = DurationParser::new();
+        assert_eq!(
+            display(&parser, "1h 30"),
+            "missing unit at 5, expected `ns`, `us`, `\u{b5}s`, `\u{3bc}s`, `ms`, `s`, `m`, `h`, `d`, `w` or `y`"
+        );
+        assert_eq!(
+            display(&parser, "1.2.3s"),
+            "invalid `1.2.3` at 0..5, must be multiples of `[0-9]+(.[0-9]+)?(ns|us|ms|[smhdwy])`"
+        );
+        assert_eq!(display(&parser, " "), "missing time duration");
+
+        let parser = parser
+            .unit_system(UnitSystem::new().months(true))
+            .custom_units(CustomUnits::new().unit(&["slot", "slots"], Duration::from_secs(12)));
+        assert_eq!(
+            display(&parser, "1 fortnight"),
+            "unknown unit `fortnight` at 2..11, expected `ns`, `us`, `\u{b5}s`, `\u{3bc}s`, `ms`, `s`, `m`, `h`, `d`, `w`, `mo`, `y`, `slot` or `slots`"
+        );
+        let unknown = display(&parser.aliases(true), "1 fortnight");
+        assert!(unknown.contains("`h`, `hr`, `hrs`, `hour`, `hours`, `d`, "));
+        assert!(unknown.ends_with("`years`, `slot` or `slots`"));
+    }
+
+    #[test]
+    fn test_micro_sign() {
+        assert_eq!(parse("5\u{b5}s"), Ok(Duration::from_micros(5)));
+        assert_eq!(parse("1ms 5\u{3bc}s"), Ok(Duration::from_micros(1005)));
+        assert_eq!(
+            parse("5\u{b5}s 1us"),
+            Err(Error::DuplicateUnit {
+                span: 6..8,
+                unit: String::from("us"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_span_precision() {
+        assert_eq!(span("1s 1.5ns"), Some(3..6));
+    }
+
+    #[test]
+    fn test_span_invalid_quantity() {
+        assert_eq!(span("1.2.3s"), Some(0..5));
+        assert_eq!(span("1s .m"), Some(3..4));
+    }
+
+    #[test]
+    fn test_span_multibyte() {
+        assert_eq!(span("1h 2ä"), Some(4..6));
+    }
+
+    #[test]
+    fn test_fraction_zeros() {
+        assert_eq!(parse("1.05s"), Ok(Duration::from_millis(1050)));
+        assert_eq!(
+            parse("1.000000000000000000000000s"),
+            Ok(Duration::from_secs(1))
+        );
+        assert_eq!(
+            parse("0.0000000000000000000001y"),
+            Err(Error::Precision { span: 0..24 })
+        );
+        assert_eq!(
+            parse("1.0000000000000000001h"),
+            Err(Error::Precision { span: 0..21 })
+        );
+    }
+
+    #[test]
+    fn test_quantity_in_nano() {
+        assert_eq!(
+            quantity_in_nano("1.25", 0..4, SECOND_IN_NANO, FORMAT),
+            Ok(1_250_000_000)
+        );
+        assert_eq!(
+            quantity_in_nano(".5", 0..2, SECOND_IN_NANO, FORMAT),
+            Ok(500_000_000)
+        );
+        assert_eq!(
+            quantity_in_nano(".", 0..1, SECOND_IN_NANO, FORMAT),
+            Err(Error::Format {
+                span: 0..1,
+                token: String::from("."),
+                expected: FORMAT,
+            })
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Finding durations in free text, such as `retry after 30s or 2m`.
+
+use crate::{DurationString, Unit};
+use std::ops::Range;
+
+/// An iterator over the durations in a text with their byte ranges, see
+/// [`DurationString::scan`].
+#[derive(Clone, Debug)]
+pub struct Scan<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Matches one `<quantity><unit>` component at `start`, returning where it ends.
+fn component(text: &str, start: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let digits = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+    let mut i = digits(start);
+    if i == start {
+        return None;
+    }
+    if bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        i = digits(i + 1);
+    }
+    let unit_end = text[i..]
+        .char_indices()
+        .find(|(_, c)| !c.is_alphabetic())
+        .map_or(text.len(), |(j, _)| i + j);
+    Unit::from_symbol(&text[i..unit_end]).map(|_| unit_end)
+}
+
+/// The adjacent components of the duration at `start`, which may be separated
+/// by whitespace but not within a component.
+fn components(text: &str, start: usize) -> Vec<Range<usize>> {
+    let mut components = Vec::new();
+    let mut i = start;
+    while let Some(end) = component(text, i) {
+        // The component must end a word, unless another one follows directly
+        // as in `1h30m`
+        match text[end..].chars().next() {
+            Some(c) if c.is_ascii_digit() && component(text, end).is_some() => {}
+            Some(c) if is_word_char(c) => break,
+            _ => {}
+        }
+        components.push(i..end);
+        i = end + (text[end..].len() - text[end..].trim_start().len());
+    }
+    components
+}
+
+impl Iterator for Scan<'_> {
+    type Item = (Range<usize>, DurationString);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+        while self.position < bytes.len() {
+            let start = self.position;
+            self.position += 1;
+            // Checked before slicing, an ASCII digit is always on a char boundary
+            if !bytes[start].is_ascii_digit() {
+                continue;
+            }
+            // A duration starts a word, so `v2s` and the `3s` of `1.3s` are skipped
+            let starts_word = self.text[..start]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !is_word_char(c) && c != '.');
+            if !starts_word {
+                continue;
+            }
+            let components = components(self.text, start);
+            // Fall back to fewer components when they do not parse together,
+            // e.g. the repeated unit of `1h 2h`
+            for last in components.iter().rev() {
+                let span = start..last.end;
+                if let Ok(duration) = self.text[span.clone()].parse::<DurationString>() {
+                    self.position = span.end;
+                    return Some((span, duration));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl DurationString {
+    /// Finds every duration in `text`, such as the `30s` and `2m` of
+    /// `retry after 30s or 2m`, along with its byte range.
+    ///
+    /// A duration is a word of the `FromStr` format without whitespace between
+    /// a quantity and its unit. Components separated by whitespace, such as
+    /// `1h 30m`, are one duration.
+    ///
+    /// ```rust
+    /// use duration_string::DurationString;
+    /// use std::time::Duration;
+    ///
+    /// let text = "retry after 30s or 2m, give up after 1h 30m";
+    /// let found: Vec<_> = DurationString::scan(text).collect();
+    /// assert_eq!(found.len(), 3);
+    /// assert_eq!(found[0], (12..15, DurationString::new(Duration::from_secs(30))));
+    /// assert_eq!(&text[found[2].0.clone()], "1h 30m");
+    /// ```
+    #[must_use]
+    pub fn scan(text: &str) -> Scan<'_> {
+        Scan { text, position: 0 }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn scan(text: &str) -> Vec<(&str, Duration)> {
+        DurationString::scan(text)
+            .map(|(span, duration)| (&text[span], duration.into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan("retry after 30s or 2m"),
+            vec![
+                ("30s", Duration::from_secs(30)),
+                ("2m", Duration::from_secs(120))
+            ]
+        );
+        assert_eq!(scan(""), vec![]);
+        assert_eq!(scan("no durations here"), vec![]);
+    }
+
+    #[test]
+    fn test_scan_compound() {
+        assert_eq!(
+            scan("wait 1h 30m, then 1h30m15s."),
+            vec![
+                ("1h 30m", Duration::from_secs(5400)),
+                ("1h30m15s", Duration::from_secs(5415))
+            ]
+        );
+        assert_eq!(scan("(1.5s)"), vec![("1.5s", Duration::from_millis(1500))]);
+        assert_eq!(
+            scan("`5\u{b5}s`"),
+            vec![("5\u{b5}s", Duration::from_micros(5))]
+        );
+    }
+
+    #[test]
+    fn test_scan_word_boundaries() {
+        assert_eq!(scan("v2s 1.2.3s 3 s 10x 5ms_ 7hours"), vec![]);
+        assert_eq!(scan("1h2"), vec![]);
+        assert_eq!(scan("-5m"), vec![("5m", Duration::from_secs(300))]);
+    }
+
+    #[test]
+    fn test_scan_fallback() {
+        assert_eq!(
+            scan("1h 2h"),
+            vec![
+                ("1h", Duration::from_secs(3600)),
+                ("2h", Duration::from_secs(7200))
+            ]
+        );
+        assert_eq!(
+            scan("every 5m 99999999999999999999y 1s"),
+            vec![
+                ("5m", Duration::from_secs(300)),
+                ("1s", Duration::from_secs(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_non_ascii() {
+        assert_eq!(scan("\u{e9} 1s"), vec![("1s", Duration::from_secs(1))]);
+        assert_eq!(
+            scan("retry in 5 minutes \u{2014} or 30s"),
+            vec![("30s", Duration::from_secs(30))]
+        );
+        assert_eq!(scan("1s \u{b5}"), vec![("1s", Duration::from_secs(1))]);
+        assert_eq!(scan("\u{e9}5s"), vec![]);
+        assert_eq!(
+            DurationString::scan("\u{e9} 1s")
+                .next()
+                .map(|(span, _)| span),
+            Some(3..5)
+        );
+    }
+
+    #[test]
+    fn test_scan_spans() {
+        let spans: Vec<_> = DurationString::scan("a 1s b 20ms")
+            .map(|(span, _)| span)
+            .collect();
+        assert_eq!(spans, vec![2..4, 7..11]);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::{DurationParser, DurationString, Error, Result};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A duration that can be negative, written with an optional sign such as `-5m` or `+2h`.
+///
+/// ```rust
+/// use duration_string::{DurationString, SignedDurationString};
+/// use std::convert::TryFrom;
+/// use std::time::Duration;
+///
+/// let offset: SignedDurationString = "-5m".parse().unwrap();
+/// assert!(offset.is_negative());
+/// assert_eq!(offset.to_string(), "-5m");
+///
+/// let d = offset + DurationString::new(Duration::from_secs(3600));
+/// assert_eq!(Duration::try_from(d).unwrap(), Duration::from_secs(3300));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct SignedDurationString {
+    // Zero is never negative, so the derived `Eq` and `Hash` hold
+    negative: bool,
+    magnitude: Duration,
+}
+
+impl SignedDurationString {
+    /// Creates a duration of `magnitude`, negative if `negative` is set and
+    /// `magnitude` is not zero.
+    #[must_use]
+    pub const fn new(negative: bool, magnitude: Duration) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    pub fn from_string(duration: String) -> Result<Self> {
+        SignedDurationString::try_from(duration)
+    }
+
+    #[must_use]
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The duration without its sign.
+    #[must_use]
+    pub const fn abs(&self) -> DurationString {
+        DurationString::new(self.magnitude)
+    }
+
+    /// Adds `other`, returning `None` if the result overflows.
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.negative == other.negative {
+            let magnitude = self.magnitude.checked_add(other.magnitude)?;
+            return Some(Self::new(self.negative, magnitude));
+        }
+        // Opposite signs, the larger magnitude decides the sign of the result
+        let negative = if self.magnitude >= other.magnitude {
+            self.negative
+        } else {
+            other.negative
+        };
+        Some(Self::new(
+            negative,
+            self.magnitude.abs_diff(other.magnitude),
+        ))
+    }
+
+    /// Subtracts `other`, returning `None` if the result overflows.
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(-other)
+    }
+}
+
+impl std::fmt::Display for SignedDurationString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.abs())
+    }
+}
+
+impl FromStr for SignedDurationString {
+    type Err = Error;
+
+    fn from_str(duration: &str) -> std::result::Result<Self, Self::Err> {
+        DurationParser::new().parse_signed(duration)
+    }
+}
+
+impl TryFrom<String> for SignedDurationString {
+    type Error = Error;
+
+    fn try_from(duration: String) -> std::result::Result<Self, Self::Error> {
+        duration.parse()
+    }
+}
+
+impl From<Duration> for SignedDurationString {
+    fn from(duration: Duration) -> Self {
+        Self::new(false, duration)
+    }
+}
+
+impl From<DurationString> for SignedDurationString {
+    fn from(duration: DurationString) -> Self {
+        Self::new(false, duration.into())
+    }
+}
+
+impl TryFrom<SignedDurationString> for Duration {
+    type Error = Error;
+
+    fn try_from(value: SignedDurationString) -> std::result::Result<Self, Self::Error> {
+        if value.negative {
+            return Err(Error::Negative);
+        }
+        Ok(value.magnitude)
+    }
+}
+
+impl TryFrom<SignedDurationString> for DurationString {
+    type Error = Error;
+
+    fn try_from(value: SignedDurationString) -> std::result::Result<Self, Self::Error> {
+        Duration::try_from(value).map(DurationString::new)
+    }
+}
+
+impl PartialOrd for SignedDurationString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SignedDurationString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl Neg for SignedDurationString {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(!self.negative, self.magnitude)
+    }
+}
+
+impl<T: Into<SignedDurationString>> Add<T> for SignedDurationString {
+    type Output = Self;
+
+    fn add(self, other: T) -> Self::Output {
+        self.checked_add(other.into())
+            .expect("overflow when adding durations")
+    }
+}
+
+impl Add<SignedDurationString> for DurationString {
+    type Output = SignedDurationString;
+
+    fn add(self, other: SignedDurationString) -> Self::Output {
+        SignedDurationString::from(self) + other
+    }
+}
+
+impl Add<SignedDurationString> for Duration {
+    type Output = SignedDurationString;
+
+    fn add(self, other: SignedDurationString) -> Self::Output {
+        SignedDurationString::from(self) + other
+    }
+}
+
+impl<T: Into<SignedDurationString>> AddAssign<T> for SignedDurationString {
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Into<SignedDurationString>> Sub<T> for SignedDurationString {
+    type Output = Self;
+
+    fn sub(self, other: T) -> Self::Output {
+        self.checked_sub(other.into())
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl Sub<SignedDurationString> for DurationString {
+    type Output = SignedDurationString;
+
+    fn sub(self, other: SignedDurationString) -> Self::Output {
+        SignedDurationString::from(self) - other
+    }
+}
+
+impl Sub<SignedDurationString> for Duration {
+    type Output = SignedDurationString;
+
+    fn sub(self, other: SignedDurationString) -> Self::Output {
+        SignedDurationString::from(self) - other
+    }
+}
+
+impl<T: Into<SignedDurationString>> SubAssign<T> for SignedDurationString {
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SignedDurationString {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(|err: Error| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&err.to_string()), &"string")
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SignedDurationString {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+
+    fn signed(s: &str) -> SignedDurationString {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(
+            signed("-5m"),
+            SignedDurationString::new(true, Duration::from_secs(300))
+        );
+        assert_eq!(
+            signed("+2h"),
+            SignedDurationString::new(false, Duration::from_secs(7200))
+        );
+        assert_eq!(
+            signed(" - 1h30m"),
+            SignedDurationString::new(true, Duration::from_secs(5400))
+        );
+        assert_eq!(signed("1s"), Duration::from_secs(1).into());
+    }
+
+    #[test]
+    fn test_from_string_negative_zero() {
+        let zero = signed("-0s");
+        assert!(!zero.is_negative());
+        assert_eq!(zero, SignedDurationString::default());
+    }
+
+    #[test]
+    fn test_from_string_error_span() {
+        assert_eq!(
+            "-5x".parse::<SignedDurationString>(),
+            Err(Error::UnknownUnit {
+                span: 2..3,
+                unit: String::from("x"),
+                expected: DurationParser::new().expected_units(),
+            })
+        );
+        assert_eq!("-".parse::<SignedDurationString>(), Err(Error::Empty));
+        assert_eq!(
+            "--5m".parse::<SignedDurationString>(),
+            Err(Error::UnknownUnit {
+                span: 1..2,
+                unit: String::from("-"),
+                expected: DurationParser::new().expected_units(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(signed("-90s").to_string(), "-1m30s");
+        assert_eq!(signed("+90s").to_string(), "1m30s");
+        assert_eq!(SignedDurationString::default().to_string(), "0s");
+    }
+
+    #[test]
+    fn test_try_into_unsigned() {
+        assert_eq!(
+            Duration::try_from(signed("5m")),
+            Ok(Duration::from_secs(300))
+        );
+        assert_eq!(
+            DurationString::try_from(signed("5m")),
+            Ok(DurationString::new(Duration::from_secs(300)))
+        );
+        assert_eq!(Duration::try_from(signed("-5m")), Err(Error::Negative));
+        assert_eq!(
+            DurationString::try_from(signed("-5m")),
+            Err(Error::Negative)
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut durations = vec![signed("1m"), signed("-1s"), signed("0s"), signed("-1m")];
+        durations.sort();
+        assert_eq!(
+            durations,
+            vec![signed("-1m"), signed("-1s"), signed("0s"), signed("1m")]
+        );
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-signed("5m"), signed("-5m"));
+        assert_eq!(-signed("-5m"), signed("5m"));
+        assert_eq!(-signed("0s"), signed("0s"));
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(signed("-5m") + signed("2m"), signed("-3m"));
+        assert_eq!(signed("-5m") + signed("-2m"), signed("-7m"));
+        assert_eq!(signed("-5m") + Duration::from_secs(600), signed("5m"));
+        assert_eq!(
+            signed("-5m") + DurationString::new(Duration::from_secs(300)),
+            signed("0s")
+        );
+        assert_eq!(
+            DurationString::new(Duration::from_secs(60)) + signed("-5m"),
+            signed("-4m")
+        );
+        assert_eq!(Duration::from_secs(60) + signed("-5m"), signed("-4m"));
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut d = signed("-5m");
+        d += Duration::from_secs(60);
+        d += signed("-1m");
+        d += DurationString::new(Duration::from_secs(600));
+        assert_eq!(d, signed("5m"));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(signed("1m") - signed("5m"), signed("-4m"));
+        assert_eq!(signed("-1m") - signed("-5m"), signed("4m"));
+        assert_eq!(signed("1m") - Duration::from_secs(60), signed("0s"));
+        assert_eq!(
+            DurationString::new(Duration::from_secs(60)) - signed("5m"),
+            signed("-4m")
+        );
+        assert_eq!(Duration::from_secs(60) - signed("-5m"), signed("6m"));
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let mut d = signed("5m");
+        d -= Duration::from_secs(600);
+        d -= signed("-1m");
+        d -= DurationString::new(Duration::from_secs(60));
+        assert_eq!(d, signed("-5m"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let d: SignedDurationString = serde_json::from_str(r#""-1m30s""#).unwrap();
+        assert_eq!(d, signed("-90s"));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""-1m30s""#);
+        serde_json::from_str::<SignedDurationString>(r#""-1x""#).expect_err("invalid unit");
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        let max = SignedDurationString::from(Duration::MAX);
+        assert_eq!(max.checked_add(signed("1ns")), None);
+        assert_eq!((-max).checked_sub(signed("1ns")), None);
+        assert_eq!(max.checked_sub(max), Some(signed("0s")));
+    }
+}
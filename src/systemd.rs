@@ -0,0 +1,330 @@
+//! systemd.time(7) timespans such as `5min 20s`, `2weeks` or `infinity`.
+
+use crate::parser::{duration_from_nanos, quantity_in_nano};
+use crate::unit::{
+    DAY_IN_NANO, HOUR_IN_NANO, MICROSECOND_IN_NANO, MILLISECOND_IN_NANO, MINUTE_IN_NANO,
+    SECOND_IN_NANO, WEEK_IN_NANO,
+};
+use crate::{expected_units, DurationString, Error, Result};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The format of systemd timespans, as the `expected` of [`Error::Format`].
+const SYSTEMD_FORMAT: &str = "a systemd time span such as `5min 20s`";
+
+/// systemd's month of 30.44 days.
+const SYSTEMD_MONTH_IN_NANO: u128 = 2_629_800 * SECOND_IN_NANO;
+/// systemd's year of 365.25 days.
+const SYSTEMD_YEAR_IN_NANO: u128 = 31_557_600 * SECOND_IN_NANO;
+
+/// Every unit systemd accepts, units are case sensitive so `M` is a month.
+const SYSTEMD_UNITS: [(&str, u128); 30] = [
+    ("usec", MICROSECOND_IN_NANO),
+    ("us", MICROSECOND_IN_NANO),
+    ("\u{b5}s", MICROSECOND_IN_NANO),
+    ("\u{3bc}s", MICROSECOND_IN_NANO),
+    ("msec", MILLISECOND_IN_NANO),
+    ("ms", MILLISECOND_IN_NANO),
+    ("seconds", SECOND_IN_NANO),
+    ("second", SECOND_IN_NANO),
+    ("sec", SECOND_IN_NANO),
+    ("s", SECOND_IN_NANO),
+    ("minutes", MINUTE_IN_NANO),
+    ("minute", MINUTE_IN_NANO),
+    ("min", MINUTE_IN_NANO),
+    ("m", MINUTE_IN_NANO),
+    ("hours", HOUR_IN_NANO),
+    ("hour", HOUR_IN_NANO),
+    ("hr", HOUR_IN_NANO),
+    ("h", HOUR_IN_NANO),
+    ("days", DAY_IN_NANO),
+    ("day", DAY_IN_NANO),
+    ("d", DAY_IN_NANO),
+    ("weeks", WEEK_IN_NANO),
+    ("week", WEEK_IN_NANO),
+    ("w", WEEK_IN_NANO),
+    ("months", SYSTEMD_MONTH_IN_NANO),
+    ("month", SYSTEMD_MONTH_IN_NANO),
+    ("M", SYSTEMD_MONTH_IN_NANO),
+    ("years", SYSTEMD_YEAR_IN_NANO),
+    ("year", SYSTEMD_YEAR_IN_NANO),
+    ("y", SYSTEMD_YEAR_IN_NANO),
+];
+
+/// The units systemd writes, from largest to smallest.
+const SYSTEMD_OUTPUT_UNITS: [(&str, u128); 9] = [
+    ("y", SYSTEMD_YEAR_IN_NANO),
+    ("month", SYSTEMD_MONTH_IN_NANO),
+    ("w", WEEK_IN_NANO),
+    ("d", DAY_IN_NANO),
+    ("h", HOUR_IN_NANO),
+    ("min", MINUTE_IN_NANO),
+    ("s", SECOND_IN_NANO),
+    ("ms", MILLISECOND_IN_NANO),
+    ("us", MICROSECOND_IN_NANO),
+];
+
+/// A systemd timespan, as used by `TimeoutStartSec=` or `RuntimeMaxSec=`.
+///
+/// Parsing accepts the whole systemd.time(7) vocabulary, such as `5min 20s`,
+/// `1h 30min`, `2weeks` or `500msec`, a bare number is seconds. Whitespace is
+/// allowed between and within components. A month is 30.44 days and a year
+/// 365.25 days, like systemd.
+///
+/// Displaying writes units systemd accepts, truncated to its microsecond
+/// precision.
+///
+/// ```rust
+/// use duration_string::SystemdTimespan;
+/// use std::time::Duration;
+///
+/// let timespan: SystemdTimespan = "1h 30min".parse().unwrap();
+/// assert_eq!(timespan.finite().unwrap(), Duration::from_secs(5400));
+/// assert_eq!(timespan.to_string(), "1h 30min");
+///
+/// let timespan: SystemdTimespan = "infinity".parse().unwrap();
+/// assert!(timespan.is_infinite());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SystemdTimespan {
+    Finite(DurationString),
+    /// systemd's `infinity`, which disables a timeout.
+    Infinity,
+}
+
+impl SystemdTimespan {
+    #[must_use]
+    pub const fn is_infinite(&self) -> bool {
+        matches!(self, Self::Infinity)
+    }
+
+    /// The duration, or `None` for [`SystemdTimespan::Infinity`].
+    #[must_use]
+    pub const fn finite(&self) -> Option<DurationString> {
+        match self {
+            Self::Finite(duration) => Some(*duration),
+            Self::Infinity => None,
+        }
+    }
+}
+
+/// Every unit systemd accepts, as the `expected` of unit errors.
+fn systemd_units() -> Vec<String> {
+    expected_units(SYSTEMD_UNITS.iter().map(|(symbol, _)| *symbol))
+}
+
+fn parse_timespan(input: &str) -> Result<DurationString> {
+    let bytes = input.as_bytes();
+    let skip_whitespace = |mut i: usize| {
+        while i < input.len() {
+            match input[i..].chars().next() {
+                Some(c) if c.is_whitespace() => i += c.len_utf8(),
+                _ => break,
+            }
+        }
+        i
+    };
+    let mut i = skip_whitespace(0);
+    if i == input.len() {
+        return Err(Error::Empty);
+    }
+    let mut total: u128 = 0;
+    while i < input.len() {
+        let start = i;
+        while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+            i += 1;
+        }
+        if start == i {
+            // Checked above, `i` is never at the end of the input here
+            let c = input[i..].chars().next().unwrap_or_default();
+            if c.is_alphabetic() {
+                return Err(Error::MissingQuantity { span: i..i });
+            }
+            return Err(Error::Format {
+                span: i..i + c.len_utf8(),
+                token: c.to_string(),
+                expected: SYSTEMD_FORMAT,
+            });
+        }
+        let quantity = start..i;
+
+        i = skip_whitespace(i);
+        let unit_start = i;
+        while let Some(c) = input[i..].chars().next().filter(|c| c.is_alphabetic()) {
+            i += c.len_utf8();
+        }
+        let unit = &input[unit_start..i];
+        let unit_in_nano = if unit.is_empty() {
+            SECOND_IN_NANO
+        } else {
+            SYSTEMD_UNITS
+                .iter()
+                .find(|(symbol, _)| *symbol == unit)
+                .map(|(_, unit_in_nano)| *unit_in_nano)
+                .ok_or_else(|| Error::UnknownUnit {
+                    span: unit_start..i,
+                    unit: unit.to_string(),
+                    expected: systemd_units(),
+                })?
+        };
+        let nanos = quantity_in_nano(
+            &input[quantity.clone()],
+            quantity,
+            unit_in_nano,
+            SYSTEMD_FORMAT,
+        )?;
+        total = total.checked_add(nanos).ok_or(Error::Overflow)?;
+        i = skip_whitespace(i);
+    }
+    duration_from_nanos(total).map(DurationString::new)
+}
+
+impl FromStr for SystemdTimespan {
+    type Err = Error;
+
+    fn from_str(timespan: &str) -> std::result::Result<Self, Self::Err> {
+        if timespan.trim() == "infinity" {
+            return Ok(Self::Infinity);
+        }
+        parse_timespan(timespan).map(Self::Finite)
+    }
+}
+
+impl std::fmt::Display for SystemdTimespan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self::Finite(duration) = self else {
+            return write!(f, "infinity");
+        };
+        let mut remainder = duration.as_nanos();
+        if remainder < MICROSECOND_IN_NANO {
+            return write!(f, "0");
+        }
+        let mut separator = "";
+        for (symbol, unit_in_nano) in SYSTEMD_OUTPUT_UNITS {
+            let count = remainder / unit_in_nano;
+            remainder %= unit_in_nano;
+            if count > 0 {
+                write!(f, "{separator}{count}{symbol}")?;
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<DurationString> for SystemdTimespan {
+    fn from(duration: DurationString) -> Self {
+        Self::Finite(duration)
+    }
+}
+
+impl From<Duration> for SystemdTimespan {
+    fn from(duration: Duration) -> Self {
+        Self::Finite(DurationString::new(duration))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+
+    fn timespan(input: &str) -> Result<Duration> {
+        input
+            .parse::<SystemdTimespan>()
+            .map(|timespan| timespan.finite().unwrap().into())
+    }
+
+    #[test]
+    fn test_parse_timespan() {
+        assert_eq!(timespan("5min 20s"), Ok(Duration::from_secs(320)));
+        assert_eq!(timespan("1h 30min"), Ok(Duration::from_secs(5400)));
+        assert_eq!(timespan("2weeks"), Ok(Duration::from_secs(1_209_600)));
+        assert_eq!(timespan("500msec"), Ok(Duration::from_millis(500)));
+        assert_eq!(timespan("2h30m"), Ok(Duration::from_secs(9000)));
+        assert_eq!(timespan("1y 12month"), Ok(Duration::from_secs(63_115_200)));
+        assert_eq!(timespan("1M"), Ok(Duration::from_secs(2_629_800)));
+        assert_eq!(timespan("1.5 hours"), Ok(Duration::from_secs(5400)));
+        assert_eq!(timespan("10\u{b5}s 5usec"), Ok(Duration::from_micros(15)));
+    }
+
+    #[test]
+    fn test_parse_timespan_default_unit() {
+        assert_eq!(timespan("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(timespan(" 1.5 "), Ok(Duration::from_millis(1500)));
+        assert_eq!(timespan("1min 30"), Ok(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_parse_timespan_infinity() {
+        assert_eq!(
+            " infinity ".parse::<SystemdTimespan>(),
+            Ok(SystemdTimespan::Infinity)
+        );
+        assert_eq!(
+            "5infinity".parse::<SystemdTimespan>(),
+            Err(Error::UnknownUnit {
+                span: 1..9,
+                unit: String::from("infinity"),
+                expected: systemd_units(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_timespan_errors() {
+        assert_eq!(timespan("  "), Err(Error::Empty));
+        assert_eq!(timespan("min"), Err(Error::MissingQuantity { span: 0..0 }));
+        assert_eq!(
+            timespan("5 Min"),
+            Err(Error::UnknownUnit {
+                span: 2..5,
+                unit: String::from("Min"),
+                expected: systemd_units(),
+            })
+        );
+        assert_eq!(
+            timespan("-5s"),
+            Err(Error::Format {
+                span: 0..1,
+                token: String::from("-"),
+                expected: SYSTEMD_FORMAT,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_timespan_error_display() {
+        let display = |input| timespan(input).unwrap_err().to_string();
+        let unknown = display("5 Min");
+        assert!(unknown.starts_with("unknown unit `Min` at 2..5, expected `usec`, `us`, "));
+        assert!(unknown.contains("`minutes`, `minute`, `min`, `m`, "));
+        assert!(unknown.ends_with("`years`, `year` or `y`"));
+        assert_eq!(
+            display("-5s"),
+            "invalid `-` at 0..1, must be a systemd time span such as `5min 20s`"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let display = |duration| SystemdTimespan::from(duration).to_string();
+        assert_eq!(display(Duration::ZERO), "0");
+        assert_eq!(display(Duration::from_nanos(999)), "0");
+        assert_eq!(display(Duration::from_secs(320)), "5min 20s");
+        assert_eq!(display(Duration::new(63_115_200, 1_001_999)), "2y 1ms 1us");
+        assert_eq!(
+            display(Duration::from_secs(2_629_800 + 86_400)),
+            "1month 1d"
+        );
+        assert_eq!(SystemdTimespan::Infinity.to_string(), "infinity");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let duration = Duration::new(40_000_000, 123_456_000);
+        let s = SystemdTimespan::from(duration).to_string();
+        assert_eq!(s, "1y 3month 6d 9h 36min 40s 123ms 456us");
+        assert_eq!(timespan(&s), Ok(duration));
+    }
+}
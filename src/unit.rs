@@ -0,0 +1,181 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+pub(crate) const YEAR_IN_NANO: u128 = 31_556_926_000_000_000;
+/// A twelfth of a year, truncated to whole nanoseconds.
+pub(crate) const MONTH_IN_NANO: u128 = YEAR_IN_NANO / 12;
+pub(crate) const WEEK_IN_NANO: u128 = 604_800_000_000_000;
+pub(crate) const DAY_IN_NANO: u128 = 86_400_000_000_000;
+pub(crate) const HOUR_IN_NANO: u128 = 3_600_000_000_000;
+pub(crate) const MINUTE_IN_NANO: u128 = 60_000_000_000;
+pub(crate) const SECOND_IN_NANO: u128 = 1_000_000_000;
+pub(crate) const MILLISECOND_IN_NANO: u128 = 1_000_000;
+pub(crate) const MICROSECOND_IN_NANO: u128 = 1000;
+
+/// Every unit of the format, from the largest to the smallest
+pub(crate) const UNITS: [Unit; 9] = [
+    Unit::Year,
+    Unit::Week,
+    Unit::Day,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Millisecond,
+    Unit::Microsecond,
+    Unit::Nanosecond,
+];
+
+/// Every unit including [`Unit::Month`], from the largest to the smallest
+pub(crate) const UNITS_WITH_MONTHS: [Unit; 10] = [
+    Unit::Year,
+    Unit::Month,
+    Unit::Week,
+    Unit::Day,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Millisecond,
+    Unit::Microsecond,
+    Unit::Nanosecond,
+];
+
+/// A unit of the `[0-9]+(ns|us|ms|[smhdwy])` format.
+///
+/// Units are ordered by their length, so `Unit::Second < Unit::Minute`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum Unit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    /// A twelfth of a year, only used with [`UnitSystem::months`](crate::UnitSystem::months).
+    Month,
+    Year,
+}
+
+impl Unit {
+    /// The symbol of the unit, e.g. `ms` for [`Unit::Millisecond`].
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        self.symbols()[0]
+    }
+
+    /// Every symbol the unit is written as, the micro sign `µs` (U+00B5) and
+    /// the Greek letter mu `μs` (U+03BC) are accepted for microseconds.
+    pub(crate) const fn symbols(self) -> &'static [&'static str] {
+        match self {
+            Self::Nanosecond => &["ns"],
+            Self::Microsecond => &["us", "\u{b5}s", "\u{3bc}s"],
+            Self::Millisecond => &["ms"],
+            Self::Second => &["s"],
+            Self::Minute => &["m"],
+            Self::Hour => &["h"],
+            Self::Day => &["d"],
+            Self::Week => &["w"],
+            Self::Month => &["mo"],
+            Self::Year => &["y"],
+        }
+    }
+
+    /// The length of the unit in nanoseconds, with the default
+    /// [`UnitSystem`](crate::UnitSystem).
+    #[must_use]
+    pub const fn as_nanos(self) -> u128 {
+        match self {
+            Self::Nanosecond => 1,
+            Self::Microsecond => MICROSECOND_IN_NANO,
+            Self::Millisecond => MILLISECOND_IN_NANO,
+            Self::Second => SECOND_IN_NANO,
+            Self::Minute => MINUTE_IN_NANO,
+            Self::Hour => HOUR_IN_NANO,
+            Self::Day => DAY_IN_NANO,
+            Self::Week => WEEK_IN_NANO,
+            Self::Month => MONTH_IN_NANO,
+            Self::Year => YEAR_IN_NANO,
+        }
+    }
+
+    /// Long-form names and abbreviations of the unit, e.g. `min` and `minutes`
+    /// for [`Unit::Minute`], see [`DurationParser::aliases`](crate::DurationParser::aliases).
+    #[must_use]
+    pub const fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Self::Nanosecond => &[
+                "nsec",
+                "nsecs",
+                "nano",
+                "nanos",
+                "nanosecond",
+                "nanoseconds",
+            ],
+            Self::Microsecond => &[
+                "usec",
+                "usecs",
+                "micro",
+                "micros",
+                "microsecond",
+                "microseconds",
+            ],
+            Self::Millisecond => &[
+                "msec",
+                "msecs",
+                "milli",
+                "millis",
+                "millisecond",
+                "milliseconds",
+            ],
+            Self::Second => &["sec", "secs", "second", "seconds"],
+            Self::Minute => &["min", "mins", "minute", "minutes"],
+            Self::Hour => &["hr", "hrs", "hour", "hours"],
+            Self::Day => &["day", "days"],
+            Self::Week => &["wk", "wks", "week", "weeks"],
+            Self::Month => &["month", "months"],
+            Self::Year => &["yr", "yrs", "year", "years"],
+        }
+    }
+
+    /// Looks up a unit by its symbol, e.g. `ms` for [`Unit::Millisecond`].
+    #[must_use]
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        UNITS_WITH_MONTHS
+            .iter()
+            .copied()
+            .find(|unit| unit.symbols().contains(&symbol))
+    }
+}
+
+impl From<Unit> for Duration {
+    /// The length of the unit with the default [`UnitSystem`](crate::UnitSystem).
+    fn from(unit: Unit) -> Self {
+        // Every unit is at most a year, which fits in a `u64` of nanoseconds
+        Duration::from_nanos(u64::try_from(unit.as_nanos()).unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::duration_suboptimal_units)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_symbol() {
+        for unit in UNITS_WITH_MONTHS {
+            assert_eq!(Unit::from_symbol(unit.symbol()), Some(unit));
+        }
+        assert_eq!(Unit::from_symbol("\u{b5}s"), Some(Unit::Microsecond));
+        assert_eq!(Unit::from_symbol("\u{3bc}s"), Some(Unit::Microsecond));
+        assert_eq!(Unit::from_symbol("x"), None);
+    }
+
+    #[test]
+    fn test_into_duration() {
+        assert_eq!(Duration::from(Unit::Nanosecond), Duration::from_nanos(1));
+        assert_eq!(Duration::from(Unit::Minute), Duration::from_secs(60));
+        assert_eq!(Duration::from(Unit::Year), Duration::from_secs(31_556_926));
+    }
+}
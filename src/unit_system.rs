@@ -0,0 +1,118 @@
+//! The lengths of years and months, which differ between tools.
+
+use crate::unit::{DAY_IN_NANO, UNITS, UNITS_WITH_MONTHS, YEAR_IN_NANO};
+use crate::Unit;
+
+/// How long a year `y` is.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum YearLength {
+    /// 365.2422 days, rounded to 31,556,926 seconds.
+    #[default]
+    Tropical,
+    /// 365.25 days, as used by systemd and Prometheus.
+    Julian,
+    /// 365 days.
+    Common,
+}
+
+impl YearLength {
+    /// The length of the year in nanoseconds.
+    #[must_use]
+    pub const fn as_nanos(self) -> u128 {
+        match self {
+            Self::Tropical => YEAR_IN_NANO,
+            Self::Julian => 365 * DAY_IN_NANO + DAY_IN_NANO / 4,
+            Self::Common => 365 * DAY_IN_NANO,
+        }
+    }
+}
+
+/// The units a [`DurationParser`](crate::DurationParser) or
+/// [`DurationFormatter`](crate::DurationFormatter) works with.
+///
+/// By default a year is [`YearLength::Tropical`] and months are not used.
+/// With [`UnitSystem::months`], `mo` is a twelfth of the year.
+///
+/// ```rust
+/// use duration_string::{DurationFormatter, DurationParser, UnitSystem, YearLength};
+/// use std::time::Duration;
+///
+/// let system = UnitSystem::new().year(YearLength::Common).months(true);
+/// let parser = DurationParser::new().unit_system(system);
+/// let d = parser.parse("1y 6mo").unwrap();
+/// assert_eq!(d, Duration::from_secs(365 * 86_400 * 3 / 2));
+///
+/// let formatter = DurationFormatter::new().unit_system(system);
+/// assert_eq!(formatter.format(d), "1y6mo");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct UnitSystem {
+    year: YearLength,
+    months: bool,
+}
+
+impl UnitSystem {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long a year is, [`YearLength::Tropical`] by default.
+    #[must_use]
+    pub fn year(mut self, year: YearLength) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Use [`Unit::Month`], written `mo`, as a twelfth of the year truncated to
+    /// whole nanoseconds.
+    #[must_use]
+    pub fn months(mut self, months: bool) -> Self {
+        self.months = months;
+        self
+    }
+
+    /// The length of `unit` in nanoseconds.
+    #[must_use]
+    pub const fn unit_in_nano(self, unit: Unit) -> u128 {
+        match unit {
+            Unit::Year => self.year.as_nanos(),
+            Unit::Month => self.year.as_nanos() / 12,
+            _ => unit.as_nanos(),
+        }
+    }
+
+    /// Every unit of the system, from the largest to the smallest.
+    pub(crate) fn units(self) -> &'static [Unit] {
+        if self.months {
+            &UNITS_WITH_MONTHS
+        } else {
+            &UNITS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unit::SECOND_IN_NANO;
+
+    #[test]
+    fn test_unit_in_nano() {
+        let system = UnitSystem::new();
+        assert_eq!(system.unit_in_nano(Unit::Year), 31_556_926 * SECOND_IN_NANO);
+        assert_eq!(system.unit_in_nano(Unit::Day), DAY_IN_NANO);
+        let system = system.year(YearLength::Julian);
+        assert_eq!(system.unit_in_nano(Unit::Year), 31_557_600 * SECOND_IN_NANO);
+        assert_eq!(system.unit_in_nano(Unit::Month), 2_629_800 * SECOND_IN_NANO);
+        let system = system.year(YearLength::Common);
+        assert_eq!(system.unit_in_nano(Unit::Year), 31_536_000 * SECOND_IN_NANO);
+        assert_eq!(system.unit_in_nano(Unit::Month), 2_628_000 * SECOND_IN_NANO);
+    }
+
+    #[test]
+    fn test_units() {
+        assert!(!UnitSystem::new().units().contains(&Unit::Month));
+        assert_eq!(UnitSystem::new().months(true).units()[1], Unit::Month);
+    }
+}